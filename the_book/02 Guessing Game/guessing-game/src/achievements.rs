@@ -0,0 +1,149 @@
+//! Achievements: named badges unlocked by finished games.
+//!
+//! Each achievement is a row in [`ACHIEVEMENTS`] with a rule over the game
+//! and the player's record, so a new one only needs a new row and its name
+//! and description in the catalogs.
+
+use crate::game::{Game, Status};
+use crate::i18n::Lang;
+use crate::insight;
+use crate::stats::Record;
+use crate::strategy::midpoint;
+
+/// What a rule gets to look at once a game is over.
+#[derive(Debug, Clone, Copy)]
+pub struct Context<'a> {
+    pub game: &'a Game,
+    /// The player's record at the game's difficulty, this game included.
+    pub record: &'a Record,
+}
+
+impl Context<'_> {
+    pub fn won(&self) -> bool {
+        self.game.status() == Status::Won
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Achievement {
+    /// The key it is stored under. Never change it once released.
+    pub id: &'static str,
+    /// Its name and what earns it, in the given language.
+    pub name: fn(Lang) -> String,
+    pub description: fn(Lang) -> String,
+    pub rule: fn(&Context) -> bool,
+}
+
+pub static ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "first_try",
+        name: |lang| lang.format("achievement_first_try", &[]),
+        description: |lang| lang.format("achievement_first_try_about", &[]),
+        rule: |c| c.won() && c.game.attempts() == 1,
+    },
+    Achievement {
+        id: "perfect_bisection",
+        name: |lang| lang.format("achievement_perfect_bisection", &[]),
+        description: |lang| lang.format("achievement_perfect_bisection_about", &[]),
+        rule: |c| c.won() && bisected(c.game),
+    },
+    Achievement {
+        id: "photo_finish",
+        name: |lang| lang.format("achievement_photo_finish", &[]),
+        description: |lang| lang.format("achievement_photo_finish_about", &[]),
+        rule: |c| c.won() && c.game.attempts_left() == Some(0),
+    },
+    Achievement {
+        id: "hot_streak",
+        name: |lang| lang.format("achievement_hot_streak", &[]),
+        description: |lang| lang.format("achievement_hot_streak_about", &[]),
+        rule: |c| c.record.streak >= 10,
+    },
+    Achievement {
+        id: "centurion",
+        name: |lang| lang.format("achievement_centurion", &[]),
+        description: |lang| lang.format("achievement_centurion_about", &[]),
+        rule: |c| c.record.played >= 100,
+    },
+];
+
+/// Looks an achievement up by its id.
+pub fn find(id: &str) -> Option<&'static Achievement> {
+    ACHIEVEMENTS.iter().find(|achievement| achievement.id == id)
+}
+
+/// Every achievement whose rule the context satisfies.
+pub fn earned<'a>(context: &'a Context) -> impl Iterator<Item = &'static Achievement> + 'a {
+    ACHIEVEMENTS
+        .iter()
+        .filter(move |achievement| (achievement.rule)(context))
+}
+
+/// Whether every guess was the middle of the numbers still possible. With
+/// an even count either of the two middle numbers will do.
+fn bisected(game: &Game) -> bool {
+    let mut low = *game.range().start();
+    let mut high = *game.range().end();
+    insight::steps(game).iter().all(|step| {
+        let middle = midpoint(low, high);
+        let even = (high - low) % 2 == 1;
+        let ok = step.guess == middle || (even && step.guess == middle + 1);
+        (low, high) = (*step.remaining.start(), *step.remaining.end());
+        ok
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn earned_ids(game: &Game, record: &Record) -> Vec<&'static str> {
+        let context = Context { game, record };
+        earned(&context).map(|achievement| achievement.id).collect()
+    }
+
+    fn played(secret: u32, max_attempts: u32, guesses: &[u32]) -> Game {
+        let mut game = Game::new(secret, 1..=100).with_max_attempts(max_attempts);
+        for &guess in guesses {
+            game.guess(guess).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn rules_look_at_the_game() {
+        let record = Record::default();
+        assert_eq!(
+            earned_ids(&played(50, 7, &[50]), &record),
+            ["first_try", "perfect_bisection"]
+        );
+        assert_eq!(
+            earned_ids(&played(60, 7, &[50, 75, 62, 56, 59, 61, 60]), &record),
+            ["perfect_bisection", "photo_finish"]
+        );
+        assert_eq!(
+            earned_ids(&played(60, 7, &[50, 76, 60]), &record),
+            Vec::<&str>::new()
+        );
+        assert!(earned_ids(&played(60, 2, &[1, 2]), &record).is_empty());
+    }
+
+    #[test]
+    fn rules_look_at_the_record() {
+        let mut record = Record::default();
+        for _ in 0..10 {
+            record.add(true, 5);
+        }
+        let game = played(60, 7, &[1, 60]);
+        assert_eq!(earned_ids(&game, &record), ["hot_streak"]);
+        record.add(false, 7);
+        assert!(earned_ids(&game, &record).is_empty());
+    }
+
+    #[test]
+    fn ids_are_unique() {
+        for achievement in ACHIEVEMENTS {
+            assert!(std::ptr::eq(find(achievement.id).unwrap(), achievement));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Batch mode: plays scripted games without a terminal, so the rules can be
+//! regression-tested from a script.
+//!
+//! The input is a JSON Lines list of games, one [`Spec`] per line, e.g.
+//!
+//! ```json
+//! {"seed": 42, "min": 1, "max": 100, "max_attempts": 7, "guesses": [50, 25, "abc", 37]}
+//! ```
+//!
+//! and the output is one [`Report`] per game, in the same order. A line that
+//! is not a valid spec gets an `{"game": N, "error": "..."}` line instead.
+
+use crate::record::Header;
+use crate::{Status, Turn};
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+
+/// One scripted game: how to set it up and what to type into it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Spec {
+    #[serde(flatten)]
+    pub header: Header,
+    pub guesses: Vec<Input>,
+}
+
+/// A guess as a number, or as a raw line to exercise input parsing the way
+/// a player's typing would, e.g. `"abc"`, `" 7 "` or `"hint parity"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Input {
+    Number(serde_json::Number),
+    Line(String),
+}
+
+impl Input {
+    pub fn line(&self) -> String {
+        match self {
+            Input::Number(number) => number.to_string(),
+            Input::Line(line) => line.clone(),
+        }
+    }
+}
+
+/// How a scripted game went.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    /// The game's position in the input, counting from 1.
+    pub game: usize,
+    pub seed: u64,
+    pub secret: u32,
+    /// `won`, `lost`, or `playing` if the guesses ran out first.
+    pub status: Status,
+    pub attempts: u32,
+    pub score: u64,
+    /// What each input did, in order.
+    pub turns: Vec<Turn>,
+}
+
+impl Spec {
+    /// Plays every input through a fresh game, even after it is over, so a
+    /// rule change that ends games sooner or later shows up in the turns.
+    pub fn play(&self, game: usize) -> Report {
+        let mut played = self.header.game();
+        let turns = self
+            .guesses
+            .iter()
+            .map(|input| played.take_turn(&input.line()))
+            .collect();
+        Report {
+            game,
+            seed: self.header.seed,
+            secret: played.secret(),
+            status: played.status(),
+            attempts: played.attempts(),
+            score: played.score(),
+            turns,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Failure {
+    game: usize,
+    error: String,
+}
+
+/// Parses one line of input and plays it as game number `game`.
+fn play_line(line: &str, game: usize) -> Result<Report, String> {
+    let spec: Spec = serde_json::from_str(line).map_err(|err| err.to_string())?;
+    let Header { min, max, .. } = spec.header;
+    if min > max {
+        return Err(format!("the range {}..={} is empty", min, max));
+    }
+    Ok(spec.play(game))
+}
+
+/// Plays every game in `input`, writing a report line for each to `out`.
+/// Blank lines are skipped. Returns how many lines were not valid specs.
+pub fn run<R: BufRead, W: Write>(input: R, mut out: W) -> io::Result<usize> {
+    let mut failures = 0;
+    let mut game = 0;
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        game += 1;
+        match play_line(&line, game) {
+            Ok(report) => serde_json::to_writer(&mut out, &report)?,
+            Err(error) => {
+                failures += 1;
+                serde_json::to_writer(&mut out, &Failure { game, error })?;
+            }
+        }
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, GuessOutcome};
+
+    fn run_str(input: &str) -> (Vec<serde_json::Value>, usize) {
+        let mut out = Vec::new();
+        let failures = run(input.as_bytes(), &mut out).unwrap();
+        let lines = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        (lines, failures)
+    }
+
+    #[test]
+    fn games_are_replayed_from_their_seed() {
+        let secret = Game::seeded(7, 1..=100).secret();
+        let input = format!(
+            r#"{{"seed": 7, "min": 1, "max": 100, "max_attempts": 3, "guesses": ["abc", 0, {}, 5]}}"#,
+            secret
+        );
+        let spec: Spec = serde_json::from_str(&input).unwrap();
+        let report = spec.play(1);
+        assert_eq!(report.secret, secret);
+        assert_eq!(report.status, Status::Won);
+        assert_eq!(report.attempts, 1);
+        assert!(matches!(report.turns[0], Turn::Invalid(_)));
+        assert!(matches!(report.turns[1], Turn::Rejected { .. }));
+        assert_eq!(
+            report.turns[2],
+            Turn::Guessed {
+                guess: secret,
+                outcome: GuessOutcome::Win
+            }
+        );
+        assert!(matches!(report.turns[3], Turn::Rejected { .. }));
+    }
+
+    #[test]
+    fn every_line_gets_a_report_in_order() {
+        let (lines, failures) = run_str(
+            "{\"seed\": 1, \"min\": 1, \"max\": 10, \"max_attempts\": 1, \"guesses\": []}\n\
+             \n\
+             not json\n\
+             {\"seed\": 3, \"min\": 9, \"max\": 1, \"max_attempts\": 1, \"guesses\": []}\n\
+             {\"seed\": 2, \"min\": 1, \"max\": 10, \"max_attempts\": null, \"guesses\": [1]}\n",
+        );
+        assert_eq!(failures, 2);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0]["status"], "playing");
+        assert_eq!(lines[1]["game"], 2);
+        assert!(lines[1]["error"].is_string());
+        assert_eq!(lines[2]["error"], "the range 9..=1 is empty");
+        assert_eq!(lines[3]["game"], 4);
+        assert_eq!(lines[3]["seed"], 2);
+    }
+}
@@ -0,0 +1,311 @@
+//! Plays many seeded games with a [`Strategy`] to compare strategies and to
+//! check that the game's feedback never contradicts itself.
+
+use crate::i18n::{Lang, Localize};
+use crate::strategy::Strategy;
+use crate::{Game, GuessOutcome};
+use std::fmt;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+/// The most guesses a strategy gets per game. A linear scan of the insane
+/// range would otherwise take billions of guesses.
+pub const MAX_GUESSES: u32 = 1_000_000;
+
+/// Attempt statistics for one strategy over a batch of games.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub strategy: String,
+    pub games: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub worst: u32,
+}
+
+/// Feedback that excluded the secret from the numbers still possible,
+/// which would mean the game's comparison logic is broken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InconsistentFeedback {
+    pub seed: u64,
+    pub secret: u32,
+    pub guess: u32,
+    pub outcome: GuessOutcome,
+}
+
+impl Localize for InconsistentFeedback {
+    fn localize(&self, lang: Lang) -> String {
+        lang.format(
+            "inconsistent_feedback",
+            &[
+                ("seed", &self.seed),
+                ("outcome", &format!("{:?}", self.outcome)),
+                ("guess", &lang.number(self.guess)),
+                ("secret", &lang.number(self.secret)),
+            ],
+        )
+    }
+}
+
+impl fmt::Display for InconsistentFeedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for InconsistentFeedback {}
+
+/// Why a benchmark game could not be scored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BenchError {
+    Inconsistent(InconsistentFeedback),
+    /// The strategy had not found the secret after [`MAX_GUESSES`] guesses.
+    GaveUp {
+        seed: u64,
+    },
+}
+
+impl Localize for BenchError {
+    fn localize(&self, lang: Lang) -> String {
+        match self {
+            BenchError::Inconsistent(err) => {
+                lang.format("bench_inconsistent", &[("error", &err.localize(lang))])
+            }
+            BenchError::GaveUp { seed } => lang.format(
+                "not_solved",
+                &[("seed", seed), ("guesses", &lang.number(MAX_GUESSES))],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for BenchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for BenchError {}
+
+/// Plays one game seeded with `seed` and returns the attempts needed.
+///
+/// Guesses are answered by [`Game::compare`], the comparison behind
+/// [`Game::guess`], so the game's own feedback is what gets checked without
+/// every guess piling up in its history.
+pub fn play(
+    strategy: &mut dyn Strategy,
+    seed: u64,
+    range: RangeInclusive<u32>,
+) -> Result<u32, BenchError> {
+    let game = Game::seeded(seed, range.clone());
+    play_against(strategy, seed, game.secret(), range, |guess| {
+        game.compare(guess)
+    })
+}
+
+/// Plays one game whose guesses are answered by `answer`, checking every
+/// answer against `secret`.
+fn play_against(
+    strategy: &mut dyn Strategy,
+    seed: u64,
+    secret: u32,
+    range: RangeInclusive<u32>,
+    answer: impl Fn(u32) -> GuessOutcome,
+) -> Result<u32, BenchError> {
+    let (mut low, mut high) = (*range.start(), *range.end());
+
+    for attempts in 1..=MAX_GUESSES {
+        // Keep a misbehaving strategy inside the interval so every game
+        // is guaranteed to finish.
+        let guess = strategy.next_guess(low, high).clamp(low, high);
+        let outcome = answer(guess);
+        let inconsistent = BenchError::Inconsistent(InconsistentFeedback {
+            seed,
+            secret,
+            guess,
+            outcome,
+        });
+        match outcome {
+            GuessOutcome::Win => return Ok(attempts),
+            GuessOutcome::TooSmall if guess < high => low = guess + 1,
+            GuessOutcome::TooBig if guess > low => high = guess - 1,
+            _ => return Err(inconsistent),
+        }
+        if !(low..=high).contains(&secret) {
+            return Err(inconsistent);
+        }
+    }
+    Err(BenchError::GaveUp { seed })
+}
+
+/// Plays `games` games seeded `seed`, `seed + 1`, ... and summarises them.
+pub fn run(
+    strategy: &mut dyn Strategy,
+    games: usize,
+    seed: u64,
+    range: RangeInclusive<u32>,
+) -> Result<Report, BenchError> {
+    let mut attempts = Vec::with_capacity(games);
+    for i in 0..games as u64 {
+        attempts.push(play(strategy, seed.wrapping_add(i), range.clone())?);
+    }
+    attempts.sort_unstable();
+
+    let mean = if games == 0 {
+        0.0
+    } else {
+        attempts.iter().map(|&a| f64::from(a)).sum::<f64>() / games as f64
+    };
+    let median = match games {
+        0 => 0.0,
+        n if n % 2 == 1 => f64::from(attempts[n / 2]),
+        n => (f64::from(attempts[n / 2 - 1]) + f64::from(attempts[n / 2])) / 2.0,
+    };
+
+    Ok(Report {
+        strategy: strategy.name().to_string(),
+        games,
+        mean,
+        median,
+        worst: attempts.last().copied().unwrap_or(0),
+    })
+}
+
+/// Formats reports as an aligned table with headers in `lang`.
+pub fn table(reports: &[Report], lang: Lang) -> String {
+    let mut out = format!(
+        "{:<12} {:>8} {:>8} {:>8} {:>8}\n",
+        lang.format("column_strategy", &[]),
+        lang.format("column_games", &[]),
+        lang.format("column_mean", &[]),
+        lang.format("column_median", &[]),
+        lang.format("column_worst", &[])
+    );
+    for report in reports {
+        let _ = writeln!(
+            out,
+            "{:<12} {:>8} {:>8.2} {:>8.1} {:>8}",
+            report.strategy, report.games, report.mean, report.median, report.worst
+        );
+    }
+    out
+}
+
+/// Formats reports as CSV with a header row.
+pub fn csv(reports: &[Report]) -> String {
+    let mut out = String::from("strategy,games,mean,median,worst\n");
+    for report in reports {
+        let _ = writeln!(
+            out,
+            "{},{},{:.4},{},{}",
+            report.strategy, report.games, report.mean, report.median, report.worst
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimal_attempts;
+    use crate::strategy::{self, Bisection, LinearScan};
+
+    #[test]
+    fn bisection_never_exceeds_the_optimum() {
+        let report = run(&mut Bisection, 1_000, 0, 1..=100).unwrap();
+        assert!(report.worst <= optimal_attempts(&(1..=100)));
+        assert!(report.mean > 1.0);
+    }
+
+    #[test]
+    fn linear_scan_needs_the_secret_minus_the_start() {
+        for seed in 0..20 {
+            let secret = Game::seeded(seed, 1..=100).secret();
+            assert_eq!(play(&mut LinearScan, seed, 1..=100), Ok(secret));
+        }
+    }
+
+    #[test]
+    fn slow_strategies_give_up_on_huge_ranges() {
+        assert_eq!(
+            play(&mut LinearScan, 0, 1..=u32::MAX),
+            Err(BenchError::GaveUp { seed: 0 })
+        );
+        assert!(play(&mut Bisection, 0, 1..=u32::MAX).unwrap() <= 32);
+    }
+
+    #[test]
+    fn broken_feedback_is_reported() {
+        let game = Game::seeded(3, 1..=100);
+        let secret = game.secret();
+        // Claims every miss is too small, so a guess above the secret
+        // pushes the lower bound past it.
+        let broken = |guess| match game.compare(guess) {
+            GuessOutcome::Win => GuessOutcome::Win,
+            _ => GuessOutcome::TooSmall,
+        };
+        match play_against(&mut Bisection, 3, secret, 1..=100, broken) {
+            Err(BenchError::Inconsistent(err)) => {
+                assert_eq!(err.secret, secret);
+                assert_eq!(err.outcome, GuessOutcome::TooSmall);
+                assert!(err.guess > secret);
+            }
+            other => panic!("expected inconsistent feedback, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn bench_answers_like_the_game() {
+        let game = Game::seeded(8, 1..=100);
+        for guess in 1..=100 {
+            assert_eq!(Ok(game.compare(guess)), game.clone().guess(guess));
+        }
+    }
+
+    #[test]
+    fn every_strategy_finishes_with_consistent_feedback() {
+        for mut strategy in strategy::all(1) {
+            let report = run(strategy.as_mut(), 200, 42, 1..=1_000).unwrap();
+            assert_eq!(report.games, 200);
+            assert!(report.median >= 1.0 && f64::from(report.worst) >= report.mean);
+        }
+    }
+
+    #[test]
+    fn runs_are_reproducible() {
+        let first = run(
+            strategy::by_name("random", 9).unwrap().as_mut(),
+            100,
+            5,
+            1..=500,
+        );
+        let second = run(
+            strategy::by_name("random", 9).unwrap().as_mut(),
+            100,
+            5,
+            1..=500,
+        );
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn formats_table_and_csv() {
+        let reports = [Report {
+            strategy: "bisection".into(),
+            games: 4,
+            mean: 5.5,
+            median: 6.0,
+            worst: 7,
+        }];
+        assert_eq!(
+            csv(&reports),
+            "strategy,games,mean,median,worst\nbisection,4,5.5000,6,7\n"
+        );
+        assert!(
+            table(&reports, Lang::En)
+                .lines()
+                .nth(1)
+                .unwrap()
+                .starts_with("bisection")
+        );
+    }
+}
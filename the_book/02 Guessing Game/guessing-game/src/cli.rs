@@ -0,0 +1,842 @@
+use guessing_game::hints::HintKind;
+use guessing_game::hotseat::{self, PlayerCountError};
+use guessing_game::i18n::{Lang, Localize, ParseLangError};
+use guessing_game::mastermind::Rules;
+use guessing_game::theme::{ColorChoice, ParseColorChoiceError};
+use guessing_game::{Difficulty, ParseDifficultyError, optimal_attempts, strategy, ulam};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// What the command line asked us to do.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Play(Box<Options>),
+    Replay { path: PathBuf, speed: f64 },
+    Batch { path: Option<PathBuf> },
+    Stats,
+    Ratings { player: Option<String> },
+    Help,
+}
+
+/// How output is coloured, whatever the command.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Appearance {
+    pub color: ColorChoice,
+    /// A built-in theme's name or the path of a theme file.
+    pub theme: Option<String>,
+}
+
+impl Appearance {
+    /// Applies `--color` or `--theme`, or returns `false` for other flags.
+    fn set(
+        &mut self,
+        flag: &str,
+        value: impl FnOnce() -> Result<String, ArgError>,
+    ) -> Result<bool, ArgError> {
+        match flag {
+            "--color" => self.color = value()?.parse().map_err(ArgError::InvalidColor)?,
+            "--theme" => self.theme = Some(value()?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+/// Settings for a single game, built from the command line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    pub difficulty: Difficulty,
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+    pub attempts: Option<u32>,
+    pub hints: bool,
+    pub insight: bool,
+    pub daily: bool,
+    pub speedrun: bool,
+    pub time_limit: Option<u64>,
+    pub seed: Option<u64>,
+    pub reveal: bool,
+    pub reverse: bool,
+    pub evil: bool,
+    pub lies: Option<u32>,
+    pub code: bool,
+    pub length: Option<usize>,
+    pub repeats: Option<bool>,
+    pub word: bool,
+    pub hard_mode: bool,
+    pub players: Option<usize>,
+    pub name: Option<String>,
+    pub record: Option<PathBuf>,
+    pub resume: Option<PathBuf>,
+    pub serve: Option<String>,
+    pub connect: Option<String>,
+    pub bench: Option<usize>,
+    pub strategies: Vec<String>,
+    pub csv: bool,
+    pub ratings: bool,
+    pub stats: bool,
+}
+
+impl Options {
+    /// The preset's range with any `--min`/`--max` overrides applied.
+    pub fn range(&self) -> RangeInclusive<u32> {
+        let preset = self.difficulty.range();
+        self.min.unwrap_or(*preset.start())..=self.max.unwrap_or(*preset.end())
+    }
+
+    /// The `--attempts` budget, or the preset's budget stretched so that a
+    /// custom range can still be won with a perfect bisection.
+    pub fn attempts(&self) -> u32 {
+        self.attempts.unwrap_or_else(|| {
+            self.difficulty
+                .attempts()
+                .max(optimal_attempts(&self.range()))
+        })
+    }
+
+    /// The shape of the code in `--code` mode: the preset's, with any
+    /// `--length` and `--repeats` overrides applied.
+    pub fn code_rules(&self) -> Rules {
+        let preset = Rules::preset(self.difficulty);
+        Rules {
+            length: self.length.unwrap_or(preset.length),
+            repeats: self.repeats.unwrap_or(preset.repeats),
+            ..preset
+        }
+    }
+
+    /// The hints on offer: none unless `--hints` was given.
+    pub fn hints(&self) -> &'static [HintKind] {
+        if self.hints {
+            self.difficulty.hints()
+        } else {
+            &[]
+        }
+    }
+
+    /// The speedrun's hard time limit, if `--time-limit` set one.
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit.map(Duration::from_secs)
+    }
+
+    /// The leaderboard category: the preset's name, or `custom` once the
+    /// range has been overridden.
+    pub fn difficulty_label(&self) -> &'static str {
+        if self.range() == self.difficulty.range() {
+            self.difficulty.name()
+        } else {
+            "custom"
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgError {
+    MissingValue(String),
+    InvalidNumber { flag: String, value: String },
+    InvalidDifficulty(ParseDifficultyError),
+    InvalidLanguage(ParseLangError),
+    InvalidColor(ParseColorChoiceError),
+    EmptyRange { min: u32, max: u32 },
+    NoAttempts,
+    NoTime,
+    MissingFile,
+    PlayerCount(PlayerCountError),
+    InvalidSpeed(String),
+    UnknownStrategy(String),
+    TooManyLies(u32),
+    Conflict(&'static str, &'static str),
+    ImpossibleCode(Rules),
+    Unknown(String),
+}
+
+impl Localize for ArgError {
+    fn localize(&self, lang: Lang) -> String {
+        match self {
+            ArgError::MissingValue(flag) => lang.format("missing_value", &[("flag", flag)]),
+            ArgError::InvalidNumber { flag, value } => {
+                lang.format("invalid_number", &[("flag", flag), ("value", value)])
+            }
+            ArgError::InvalidDifficulty(err) => err.localize(lang),
+            ArgError::InvalidLanguage(err) => err.localize(lang),
+            ArgError::InvalidColor(err) => err.localize(lang),
+            ArgError::EmptyRange { min, max } => {
+                lang.format("empty_range", &[("min", min), ("max", max)])
+            }
+            ArgError::NoAttempts => lang.format("no_attempts", &[]),
+            ArgError::NoTime => lang.format("no_time", &[]),
+            ArgError::PlayerCount(err) => err.localize(lang),
+            ArgError::MissingFile => lang.format("missing_file", &[]),
+            ArgError::InvalidSpeed(value) => lang.format("invalid_speed", &[("value", value)]),
+            ArgError::UnknownStrategy(name) => lang.format(
+                "unknown_strategy",
+                &[("name", name), ("expected", &strategy::NAMES.join(", "))],
+            ),
+            ArgError::TooManyLies(lies) => {
+                lang.format("too_many_lies", &[("max", &ulam::MAX_LIES), ("lies", lies)])
+            }
+            ArgError::Conflict(first, second) => {
+                lang.format("conflict", &[("first", first), ("second", second)])
+            }
+            ArgError::ImpossibleCode(rules) => {
+                lang.format("impossible_code", &[("rules", &rules.localize(lang))])
+            }
+            ArgError::Unknown(arg) => lang.format("unexpected_argument", &[("arg", arg)]),
+        }
+    }
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for ArgError {}
+
+/// Parses the arguments that follow the program name.
+pub fn parse<I>(args: I) -> Result<(Command, Appearance), ArgError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut appearance = Appearance::default();
+    let command = parse_command(args.into_iter(), &mut appearance)?;
+    Ok((command, appearance))
+}
+
+/// The language picked with `--lang`, found without parsing anything else
+/// so that mistakes in the other arguments can be reported in it.
+pub fn requested_lang(args: &[String]) -> Option<Lang> {
+    let mut lang = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = match split_flag(arg.clone()) {
+            (flag, inline) if flag == "--lang" => inline.or_else(|| args.next().cloned()),
+            _ => None,
+        };
+        if let Some(requested) = value.and_then(|value| value.parse().ok()) {
+            lang = Some(requested);
+        }
+    }
+    lang
+}
+
+/// Splits `--flag=value` into its flag and value, so both it and
+/// `--flag value` are accepted.
+fn split_flag(arg: String) -> (String, Option<String>) {
+    match arg.split_once('=') {
+        Some((flag, value)) if flag.starts_with("--") => {
+            (flag.to_string(), Some(value.to_string()))
+        }
+        _ => (arg, None),
+    }
+}
+
+fn parse_command<I>(mut args: I, appearance: &mut Appearance) -> Result<Command, ArgError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = Options::default();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = split_flag(arg);
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| ArgError::MissingValue(flag.clone()))
+        };
+        if appearance.set(&flag, &mut value)? {
+            continue;
+        }
+
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "replay" => return parse_replay(args, appearance),
+            "batch" => return parse_batch(args, appearance),
+            "-d" | "--difficulty" => {
+                options.difficulty = value()?.parse().map_err(ArgError::InvalidDifficulty)?
+            }
+            "--min" => options.min = Some(number(&flag, value()?)?),
+            "--max" => options.max = Some(number(&flag, value()?)?),
+            "--attempts" => options.attempts = Some(number(&flag, value()?)?),
+            "--hints" => options.hints = true,
+            "--insight" => options.insight = true,
+            "--daily" => options.daily = true,
+            "--speedrun" => options.speedrun = true,
+            "--time-limit" => {
+                options.time_limit = Some(number(&flag, value()?)?);
+                options.speedrun = true;
+            }
+            "--seed" => options.seed = Some(number(&flag, value()?)?),
+            "--reveal" | "--debug" => options.reveal = true,
+            "--reverse" => options.reverse = true,
+            "--evil" => options.evil = true,
+            "--code" => options.code = true,
+            "--length" => options.length = Some(number(&flag, value()?)?),
+            "--repeats" => options.repeats = Some(true),
+            "--no-repeats" => options.repeats = Some(false),
+            "--word" => options.word = true,
+            "--hard-mode" => options.hard_mode = true,
+            "--lies" => {
+                let lies = number(&flag, value()?)?;
+                if lies > ulam::MAX_LIES {
+                    return Err(ArgError::TooManyLies(lies));
+                }
+                options.lies = Some(lies);
+            }
+            "--players" => options.players = Some(number(&flag, value()?)?),
+            "--name" => options.name = Some(value()?),
+            // Already applied by `requested_lang`, but still rejected here
+            // if it names no language we have.
+            "--lang" => {
+                value()?
+                    .parse::<Lang>()
+                    .map_err(ArgError::InvalidLanguage)?;
+            }
+            "--record" => options.record = Some(value()?.into()),
+            "--resume" => options.resume = Some(value()?.into()),
+            "--stats" => options.stats = true,
+            "--ratings" => options.ratings = true,
+            "--serve" => options.serve = Some(value()?),
+            "--connect" => options.connect = Some(value()?),
+            "--bench" => options.bench = Some(number(&flag, value()?)?),
+            "--strategy" => {
+                let name = value()?;
+                if !strategy::NAMES.contains(&name.as_str()) {
+                    return Err(ArgError::UnknownStrategy(name));
+                }
+                options.strategies.push(name);
+            }
+            "--csv" => options.csv = true,
+            _ => return Err(ArgError::Unknown(flag)),
+        }
+    }
+
+    if options.stats {
+        return Ok(Command::Stats);
+    }
+
+    if options.ratings {
+        return Ok(Command::Ratings {
+            player: options.name,
+        });
+    }
+
+    let range = options.range();
+    if range.start() >= range.end() {
+        return Err(ArgError::EmptyRange {
+            min: *range.start(),
+            max: *range.end(),
+        });
+    }
+
+    if options.attempts == Some(0) {
+        return Err(ArgError::NoAttempts);
+    }
+
+    // Each of these picks what kind of game runs, so at most one may be given.
+    let modes = [
+        ("--speedrun", options.speedrun),
+        ("--daily", options.daily),
+        ("--players", options.players.is_some()),
+        ("--reverse", options.reverse),
+        ("--evil", options.evil),
+        ("--lies", options.lies.is_some()),
+        ("--code", options.code),
+        ("--word", options.word),
+        ("--resume", options.resume.is_some()),
+        ("--serve", options.serve.is_some()),
+        ("--connect", options.connect.is_some()),
+        ("--bench", options.bench.is_some()),
+    ];
+    let mut given = modes.iter().filter(|(_, given)| *given);
+    if let (Some((first, _)), Some((second, _))) = (given.next(), given.next()) {
+        return Err(ArgError::Conflict(first, second));
+    }
+
+    if options.daily {
+        let fixed = [
+            ("--seed", options.seed.is_some()),
+            ("--min", options.min.is_some()),
+            ("--max", options.max.is_some()),
+            ("--attempts", options.attempts.is_some()),
+        ];
+        if let Some((flag, _)) = fixed.iter().find(|(_, given)| *given) {
+            return Err(ArgError::Conflict("--daily", flag));
+        }
+    }
+
+    if options.time_limit == Some(0) {
+        return Err(ArgError::NoTime);
+    }
+
+    if options.record.is_some() {
+        // Only games with a seeded secret can be replayed.
+        let unrecordable = [
+            ("--reverse", options.reverse),
+            ("--evil", options.evil),
+            ("--lies", options.lies.is_some()),
+            ("--code", options.code),
+            ("--word", options.word),
+            ("--players", options.players.is_some()),
+            ("--resume", options.resume.is_some()),
+            ("--speedrun", options.speedrun),
+            ("--serve", options.serve.is_some()),
+            ("--connect", options.connect.is_some()),
+            ("--bench", options.bench.is_some()),
+        ];
+        if let Some((flag, _)) = unrecordable.iter().find(|(_, given)| *given) {
+            return Err(ArgError::Conflict("--record", flag));
+        }
+    }
+
+    if options.code && !options.code_rules().is_possible() {
+        return Err(ArgError::ImpossibleCode(options.code_rules()));
+    }
+
+    if let Some(players) = options.players
+        && !(hotseat::MIN_PLAYERS..=hotseat::MAX_PLAYERS).contains(&players)
+    {
+        return Err(ArgError::PlayerCount(PlayerCountError(players)));
+    }
+
+    Ok(Command::Play(Box::new(options)))
+}
+
+fn parse_replay<I>(mut args: I, appearance: &mut Appearance) -> Result<Command, ArgError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut path = None;
+    let mut speed = 1.0;
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = split_flag(arg);
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| ArgError::MissingValue(flag.clone()))
+        };
+        if appearance.set(&flag, &mut value)? {
+            continue;
+        }
+
+        match flag.as_str() {
+            "--speed" => speed = parse_speed(value()?)?,
+            "-h" | "--help" => return Ok(Command::Help),
+            _ if flag.starts_with('-') || path.is_some() => return Err(ArgError::Unknown(flag)),
+            _ => path = Some(PathBuf::from(flag)),
+        }
+    }
+
+    Ok(Command::Replay {
+        path: path.ok_or(ArgError::MissingFile)?,
+        speed,
+    })
+}
+
+fn parse_batch<I>(mut args: I, appearance: &mut Appearance) -> Result<Command, ArgError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut path = None;
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = split_flag(arg);
+        let value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| ArgError::MissingValue(flag.clone()))
+        };
+        if appearance.set(&flag, value)? {
+            continue;
+        }
+
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            _ if path.is_some() => return Err(ArgError::Unknown(flag)),
+            "-" => path = Some(None),
+            _ if flag.starts_with('-') => return Err(ArgError::Unknown(flag)),
+            _ => path = Some(Some(PathBuf::from(flag))),
+        }
+    }
+
+    Ok(Command::Batch {
+        path: path.flatten(),
+    })
+}
+
+fn parse_speed(value: String) -> Result<f64, ArgError> {
+    match value.parse::<f64>() {
+        Ok(speed) if speed >= 0.0 && speed.is_finite() => Ok(speed),
+        _ => Err(ArgError::InvalidSpeed(value)),
+    }
+}
+
+fn number<T: std::str::FromStr>(flag: &str, value: String) -> Result<T, ArgError> {
+    value.parse().map_err(|_| ArgError::InvalidNumber {
+        flag: flag.to_string(),
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Command, ArgError> {
+        parse(args.iter().map(|s| s.to_string())).map(|(command, _)| command)
+    }
+
+    fn options(args: &[&str]) -> Options {
+        match parse_args(args) {
+            Ok(Command::Play(options)) => *options,
+            other => panic!("expected options, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn defaults_to_normal() {
+        assert_eq!(options(&[]).range(), 1..=100);
+    }
+
+    #[test]
+    fn difficulty_selects_preset_range() {
+        assert_eq!(options(&["--difficulty", "hard"]).range(), 1..=10_000);
+        assert_eq!(options(&["-d", "easy"]).range(), 1..=10);
+        assert_eq!(options(&["--difficulty=insane"]).range(), 1..=u32::MAX);
+    }
+
+    #[test]
+    fn min_and_max_override_preset() {
+        assert_eq!(options(&["--min", "50", "--max=60"]).range(), 50..=60);
+        assert_eq!(options(&["-d", "easy", "--max", "20"]).range(), 1..=20);
+    }
+
+    #[test]
+    fn attempt_budget() {
+        assert_eq!(options(&[]).attempts(), 10);
+        assert_eq!(options(&["-d", "hard"]).attempts(), 16);
+        assert_eq!(options(&["--attempts", "3"]).attempts(), 3);
+        // A custom range wider than the preset gets enough for a bisection.
+        assert_eq!(options(&["-d", "easy", "--max", "1000"]).attempts(), 10);
+        assert_eq!(parse_args(&["--attempts", "0"]), Err(ArgError::NoAttempts));
+    }
+
+    #[test]
+    fn custom_ranges_get_their_own_label() {
+        assert_eq!(options(&["-d", "hard"]).difficulty_label(), "hard");
+        assert_eq!(options(&["--max", "100"]).difficulty_label(), "normal");
+        assert_eq!(options(&["--max", "50"]).difficulty_label(), "custom");
+    }
+
+    #[test]
+    fn stats_and_name() {
+        assert_eq!(parse_args(&["--stats"]), Ok(Command::Stats));
+        assert_eq!(
+            parse_args(&["--ratings", "--name", "ana"]),
+            Ok(Command::Ratings {
+                player: Some("ana".to_string())
+            })
+        );
+        assert_eq!(options(&["--name", "ana"]).name.as_deref(), Some("ana"));
+    }
+
+    #[test]
+    fn reverse_mode() {
+        assert!(options(&["--reverse", "-d", "easy"]).reverse);
+        assert!(!options(&[]).reverse);
+    }
+
+    #[test]
+    fn code_rules_override_the_preset() {
+        let rules = options(&["--code", "-d", "hard"]).code_rules();
+        assert_eq!((rules.length, rules.digits, rules.repeats), (4, 10, false));
+        let rules = options(&["--code", "-d", "hard", "--length", "6", "--repeats"]).code_rules();
+        assert_eq!((rules.length, rules.repeats), (6, true));
+        assert!(matches!(
+            parse_args(&["--code", "-d", "normal", "--length", "7"]),
+            Err(ArgError::ImpossibleCode(_))
+        ));
+        assert!(parse_args(&["--code", "-d", "easy", "--no-repeats", "--length", "6"]).is_ok());
+    }
+
+    #[test]
+    fn batch_reads_a_file_or_stdin() {
+        assert_eq!(parse_args(&["batch"]), Ok(Command::Batch { path: None }));
+        assert_eq!(
+            parse_args(&["batch", "-"]),
+            Ok(Command::Batch { path: None })
+        );
+        assert_eq!(
+            parse_args(&["batch", "games.jsonl"]),
+            Ok(Command::Batch {
+                path: Some("games.jsonl".into())
+            })
+        );
+        assert_eq!(
+            parse_args(&["batch", "a", "b"]),
+            Err(ArgError::Unknown("b".into()))
+        );
+    }
+
+    #[test]
+    fn appearance_applies_to_every_command() {
+        let appearance = |args: &[&str]| parse(args.iter().map(|s| s.to_string())).map(|(_, a)| a);
+        assert_eq!(appearance(&[]), Ok(Appearance::default()));
+        assert_eq!(
+            appearance(&["--stats", "--color", "never", "--theme=colorblind"]),
+            Ok(Appearance {
+                color: ColorChoice::Never,
+                theme: Some("colorblind".into()),
+            })
+        );
+        assert_eq!(
+            appearance(&["replay", "game.jsonl", "--color=always"]).map(|a| a.color),
+            Ok(ColorChoice::Always)
+        );
+        assert!(matches!(
+            parse_args(&["--color", "sometimes"]),
+            Err(ArgError::InvalidColor(_))
+        ));
+    }
+
+    #[test]
+    fn languages_are_parsed() {
+        let requested = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            requested_lang(&args)
+        };
+        assert_eq!(requested(&["--lang", "hi"]), Some(Lang::Hi));
+        assert_eq!(
+            requested(&["--bogus", "--lang=es", "--max"]),
+            Some(Lang::Es)
+        );
+        assert_eq!(requested(&["--lang", "fr"]), None);
+        assert_eq!(requested(&[]), None);
+        assert!(matches!(
+            parse_args(&["--lang", "fr"]),
+            Err(ArgError::InvalidLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn time_limits_imply_a_speedrun() {
+        assert!(options(&["--speedrun"]).speedrun);
+        let options = options(&["--time-limit", "90"]);
+        assert!(options.speedrun);
+        assert_eq!(options.time_limit(), Some(Duration::from_secs(90)));
+        assert_eq!(parse_args(&["--time-limit", "0"]), Err(ArgError::NoTime));
+        assert_eq!(
+            parse_args(&["--speedrun", "--word"]),
+            Err(ArgError::Conflict("--speedrun", "--word"))
+        );
+    }
+
+    #[test]
+    fn daily_puzzles_fix_the_secret_and_range() {
+        assert!(options(&["--daily"]).daily);
+        assert_eq!(
+            parse_args(&["--daily", "--seed", "3"]),
+            Err(ArgError::Conflict("--daily", "--seed"))
+        );
+        assert_eq!(
+            parse_args(&["--max=9", "--daily"]),
+            Err(ArgError::Conflict("--daily", "--max"))
+        );
+    }
+
+    #[test]
+    fn modes_cannot_be_combined() {
+        assert_eq!(
+            parse_args(&["--daily", "--players", "2"]),
+            Err(ArgError::Conflict("--daily", "--players"))
+        );
+        assert_eq!(
+            parse_args(&["--code", "--reverse"]),
+            Err(ArgError::Conflict("--reverse", "--code"))
+        );
+        assert_eq!(
+            parse_args(&["--lies", "1", "--evil", "--word"]),
+            Err(ArgError::Conflict("--evil", "--lies"))
+        );
+        assert_eq!(
+            parse_args(&["--bench", "100", "--word"]),
+            Err(ArgError::Conflict("--word", "--bench"))
+        );
+        assert_eq!(
+            parse_args(&["--resume", "f", "--evil"]),
+            Err(ArgError::Conflict("--evil", "--resume"))
+        );
+        assert_eq!(
+            parse_args(&["--daily", "--resume", "f"]),
+            Err(ArgError::Conflict("--daily", "--resume"))
+        );
+    }
+
+    #[test]
+    fn only_seeded_games_can_be_recorded() {
+        assert!(options(&["--daily", "--record", "a.jsonl"]).daily);
+        assert_eq!(
+            parse_args(&["--record", "a.jsonl", "--evil"]),
+            Err(ArgError::Conflict("--record", "--evil"))
+        );
+        assert_eq!(
+            parse_args(&["--players=2", "--record=a.jsonl"]),
+            Err(ArgError::Conflict("--record", "--players"))
+        );
+    }
+
+    #[test]
+    fn resume_takes_a_file() {
+        assert_eq!(
+            options(&["--resume", "game.save"]).resume,
+            Some(PathBuf::from("game.save"))
+        );
+        assert_eq!(
+            parse_args(&["--resume"]),
+            Err(ArgError::MissingValue("--resume".to_string()))
+        );
+    }
+
+    #[test]
+    fn word_mode() {
+        let options = options(&["--word", "--hard-mode"]);
+        assert!(options.word && options.hard_mode);
+    }
+
+    #[test]
+    fn lies_are_bounded() {
+        assert_eq!(options(&["--lies", "2"]).lies, Some(2));
+        assert_eq!(parse_args(&["--lies", "6"]), Err(ArgError::TooManyLies(6)));
+    }
+
+    #[test]
+    fn evil_mode() {
+        assert!(options(&["--evil"]).evil);
+        assert!(!options(&[]).evil);
+    }
+
+    #[test]
+    fn bench_options() {
+        let parsed = options(&[
+            "--bench",
+            "500",
+            "--strategy",
+            "linear",
+            "--strategy=biased",
+            "--csv",
+        ]);
+        assert_eq!(parsed.bench, Some(500));
+        assert_eq!(parsed.strategies, ["linear", "biased"]);
+        assert!(parsed.csv);
+        assert_eq!(
+            parse_args(&["--strategy", "psychic"]),
+            Err(ArgError::UnknownStrategy("psychic".into()))
+        );
+    }
+
+    #[test]
+    fn network_addresses() {
+        assert_eq!(
+            options(&["--serve", "0.0.0.0:7878"]).serve.as_deref(),
+            Some("0.0.0.0:7878")
+        );
+        assert_eq!(
+            options(&["--connect=localhost:7878"]).connect.as_deref(),
+            Some("localhost:7878")
+        );
+    }
+
+    #[test]
+    fn record_and_replay() {
+        assert_eq!(
+            options(&["--record", "game.jsonl"]).record,
+            Some(PathBuf::from("game.jsonl"))
+        );
+        assert_eq!(
+            parse_args(&["replay", "game.jsonl"]),
+            Ok(Command::Replay {
+                path: "game.jsonl".into(),
+                speed: 1.0
+            })
+        );
+        assert_eq!(
+            parse_args(&["replay", "--speed=0", "game.jsonl"]),
+            Ok(Command::Replay {
+                path: "game.jsonl".into(),
+                speed: 0.0
+            })
+        );
+        assert_eq!(parse_args(&["replay"]), Err(ArgError::MissingFile));
+        assert_eq!(
+            parse_args(&["replay", "a", "--speed", "-2"]),
+            Err(ArgError::InvalidSpeed("-2".into()))
+        );
+    }
+
+    #[test]
+    fn hot_seat_player_count() {
+        assert_eq!(options(&["--players", "4"]).players, Some(4));
+        assert_eq!(
+            parse_args(&["--players", "9"]),
+            Err(ArgError::PlayerCount(PlayerCountError(9)))
+        );
+        assert!(parse_args(&["--players", "1"]).is_err());
+    }
+
+    #[test]
+    fn hints_follow_the_difficulty() {
+        assert!(options(&[]).hints().is_empty());
+        assert_eq!(options(&["--hints"]).hints(), &HintKind::ALL);
+        assert_eq!(
+            options(&["--hints", "-d", "insane"]).hints(),
+            &[HintKind::Temperature]
+        );
+    }
+
+    #[test]
+    fn seed_and_reveal() {
+        let parsed = options(&["--seed", "18446744073709551615", "--debug"]);
+        assert_eq!(parsed.seed, Some(u64::MAX));
+        assert!(parsed.reveal);
+        assert_eq!(options(&[]).seed, None);
+        assert!(!options(&[]).reveal);
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert_eq!(
+            parse_args(&["--min", "ten"]),
+            Err(ArgError::InvalidNumber {
+                flag: "--min".into(),
+                value: "ten".into()
+            })
+        );
+        assert_eq!(
+            parse_args(&["--min", "10", "--max", "5"]),
+            Err(ArgError::EmptyRange { min: 10, max: 5 })
+        );
+        assert_eq!(
+            parse_args(&["--max"]),
+            Err(ArgError::MissingValue("--max".into()))
+        );
+        assert!(matches!(
+            parse_args(&["-d", "impossible"]),
+            Err(ArgError::InvalidDifficulty(_))
+        ));
+        assert!(matches!(
+            parse_args(&["--seed", "-1"]),
+            Err(ArgError::InvalidNumber { .. })
+        ));
+        assert_eq!(
+            parse_args(&["--bogus"]),
+            Err(ArgError::Unknown("--bogus".into()))
+        );
+    }
+}
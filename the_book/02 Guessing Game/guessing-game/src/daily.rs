@@ -0,0 +1,222 @@
+//! The daily challenge: one puzzle per UTC day, the same for everyone.
+
+use crate::game::{Game, GuessOutcome, Status, optimal_attempts};
+use rand::prng::ChaChaRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The upper ends the daily range is picked from.
+const MAXES: [u32; 5] = [50, 100, 200, 500, 1000];
+
+/// Guesses allowed beyond a perfect bisection of the day's range.
+const SLACK: u32 = 3;
+
+/// The day of puzzle #1.
+const FIRST_DAY: Date = Date {
+    year: 2024,
+    month: 1,
+    day: 1,
+};
+
+/// A day in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Today's date in UTC.
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        Date::from_days((seconds / 86_400) as i64)
+    }
+
+    /// The date `days` days after 1970-01-01.
+    pub fn from_days(days: i64) -> Date {
+        // Howard Hinnant's civil_from_days, with years starting in March so
+        // the leap day comes last.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+
+    /// The number of days since 1970-01-01.
+    pub fn days(&self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+            + i64::from(self.day)
+            - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// The puzzle for one day. Its range and secret depend on nothing but the
+/// date, so everyone playing on the same UTC day gets the same game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Puzzle {
+    pub date: Date,
+}
+
+impl Puzzle {
+    pub fn today() -> Puzzle {
+        Puzzle {
+            date: Date::today(),
+        }
+    }
+
+    /// Puzzles are numbered from 1 on 2024-01-01.
+    pub fn number(&self) -> i64 {
+        self.date.days() - FIRST_DAY.days() + 1
+    }
+
+    fn rng(&self) -> ChaChaRng {
+        ChaChaRng::seed_from_u64(self.date.days() as u64)
+    }
+
+    pub fn range(&self) -> RangeInclusive<u32> {
+        let max = MAXES[self.rng().gen_range(0, MAXES.len())];
+        1..=max
+    }
+
+    /// The seed for the day's secret.
+    pub fn seed(&self) -> u64 {
+        let mut rng = self.rng();
+        rng.gen_range(0, MAXES.len());
+        rng.r#gen()
+    }
+
+    pub fn attempts(&self) -> u32 {
+        optimal_attempts(&self.range()) + SLACK
+    }
+
+    pub fn game(&self) -> Game {
+        Game::seeded(self.seed(), self.range()).with_max_attempts(self.attempts())
+    }
+
+    /// A summary that can be posted without spoiling the puzzle: the number
+    /// of attempts and which way each guess was off, but no numbers.
+    pub fn share_line(&self, game: &Game) -> String {
+        let score = match game.status() {
+            Status::Won => game.attempts().to_string(),
+            _ => "X".to_string(),
+        };
+        let arrows: String = game
+            .history()
+            .iter()
+            .map(
+                |guess| match GuessOutcome::from(guess.cmp(&game.secret())) {
+                    GuessOutcome::TooSmall => "⬆️",
+                    GuessOutcome::TooBig => "⬇️",
+                    GuessOutcome::Win => "✅",
+                },
+            )
+            .collect();
+        format!(
+            "Guessing Game #{} {}/{} {}",
+            self.number(),
+            score,
+            self.attempts(),
+            arrows
+        )
+    }
+}
+
+/// How a player did on one day's puzzle. It is stored as soon as the
+/// puzzle is started, so quitting halfway does not earn a second try.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub finished: bool,
+    pub won: bool,
+    pub attempts: u32,
+    pub share: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_round_trip_through_days() {
+        assert_eq!(Date::from_days(0).to_string(), "1970-01-01");
+        assert_eq!(FIRST_DAY.days(), 19_723);
+        let leap = Date {
+            year: 2024,
+            month: 2,
+            day: 29,
+        };
+        assert_eq!(Date::from_days(leap.days()), leap);
+        for days in (-800_000..800_000).step_by(997) {
+            assert_eq!(Date::from_days(days).days(), days);
+        }
+    }
+
+    #[test]
+    fn the_puzzle_only_depends_on_the_date() {
+        let date = Date {
+            year: 2026,
+            month: 10,
+            day: 17,
+        };
+        let puzzle = Puzzle { date };
+        assert_eq!(puzzle.number(), 1021);
+        assert_eq!(puzzle.game().secret(), Puzzle { date }.game().secret());
+        assert!(MAXES.contains(puzzle.range().end()));
+
+        let secrets: Vec<u32> = (0..10)
+            .map(|offset| {
+                Puzzle {
+                    date: Date::from_days(date.days() + offset),
+                }
+                .game()
+                .secret()
+            })
+            .collect();
+        assert!(secrets.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn share_lines_hide_the_numbers() {
+        let puzzle = Puzzle { date: FIRST_DAY };
+        let mut game = Game::new(40, 1..=100).with_max_attempts(puzzle.attempts());
+        for guess in [50, 25, 40] {
+            game.guess(guess).unwrap();
+        }
+        let line = puzzle.share_line(&game);
+        assert!(line.starts_with("Guessing Game #1 3/"), "{}", line);
+        assert!(line.ends_with(" ⬇️⬆️✅"), "{}", line);
+        assert!(!line.contains("40"));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::hints::HintKind;
+use crate::i18n::{Lang, Localize};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Named presets for the range the secret is drawn from and how many
+/// attempts the player gets to find it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Insane,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Insane,
+    ];
+
+    pub fn range(self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 1..=10,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=10_000,
+            Difficulty::Insane => 1..=u32::MAX,
+        }
+    }
+
+    /// The attempt budget for this preset. Every preset leaves at least
+    /// enough attempts for a perfect bisection of its range.
+    pub fn attempts(self) -> u32 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => 10,
+            Difficulty::Hard => 16,
+            Difficulty::Insane => 32,
+        }
+    }
+
+    /// The kinds of hint offered when hints are turned on.
+    pub fn hints(self) -> &'static [HintKind] {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => &HintKind::ALL,
+            Difficulty::Hard => &[HintKind::Temperature, HintKind::Parity],
+            Difficulty::Insane => &[HintKind::Temperature],
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Insane => "insane",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Returned when a string does not name one of the difficulty presets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDifficultyError(String);
+
+impl Localize for ParseDifficultyError {
+    fn localize(&self, lang: Lang) -> String {
+        lang.format("unknown_difficulty", &[("name", &self.0)])
+    }
+}
+
+impl fmt::Display for ParseDifficultyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for ParseDifficultyError {}
+
+impl FromStr for Difficulty {
+    type Err = ParseDifficultyError;
+
+    fn from_str(s: &str) -> Result<Difficulty, ParseDifficultyError> {
+        Difficulty::ALL
+            .iter()
+            .copied()
+            .find(|d| d.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseDifficultyError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_have_expected_ranges() {
+        assert_eq!(Difficulty::Easy.range(), 1..=10);
+        assert_eq!(Difficulty::Normal.range(), 1..=100);
+        assert_eq!(Difficulty::Hard.range(), 1..=10_000);
+        assert_eq!(Difficulty::Insane.range(), 1..=u32::MAX);
+    }
+
+    #[test]
+    fn budgets_allow_a_perfect_bisection() {
+        for difficulty in Difficulty::ALL.iter() {
+            let needed = crate::optimal_attempts(&difficulty.range());
+            assert!(difficulty.attempts() >= needed, "{}", difficulty);
+        }
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for difficulty in Difficulty::ALL.iter() {
+            assert_eq!(difficulty.name().parse(), Ok(*difficulty));
+        }
+        assert_eq!("HARD".parse(), Ok(Difficulty::Hard));
+        assert!("impossible".parse::<Difficulty>().is_err());
+    }
+}
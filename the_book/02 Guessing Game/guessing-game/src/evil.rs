@@ -0,0 +1,191 @@
+//! Evil host mode: the secret is not picked until the player forces it.
+//!
+//! The host only keeps the interval of numbers that agree with every answer
+//! so far, and answers each guess so that the larger half survives. A player
+//! who bisects still wins in `optimal_attempts` guesses, anyone else pays for
+//! every lopsided guess. The host never lies: whatever number it settles on
+//! compares with each guess exactly as it answered.
+
+use crate::game::{GuessError, GuessOutcome, Status, Turn};
+use crate::input::parse_guess;
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone)]
+pub struct EvilHost {
+    range: RangeInclusive<u32>,
+    low: u32,
+    high: u32,
+    max_attempts: Option<u32>,
+    answers: Vec<(u32, GuessOutcome)>,
+}
+
+impl EvilHost {
+    pub fn new(range: RangeInclusive<u32>) -> EvilHost {
+        assert!(!range.is_empty(), "the range must not be empty");
+        EvilHost {
+            low: *range.start(),
+            high: *range.end(),
+            range,
+            max_attempts: None,
+            answers: Vec::new(),
+        }
+    }
+
+    /// Limits the player to `max_attempts` guesses.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> EvilHost {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Answers a guess so that as many numbers as possible stay possible.
+    /// A guess outside the remaining interval has only one honest answer.
+    pub fn guess(&mut self, guess: u32) -> Result<GuessOutcome, GuessError> {
+        if self.status() != Status::Playing {
+            return Err(GuessError::GameOver);
+        }
+        if !self.range.contains(&guess) {
+            return Err(GuessError::OutOfRange {
+                guess,
+                range: self.range.clone(),
+            });
+        }
+
+        let outcome = if guess < self.low {
+            GuessOutcome::TooSmall
+        } else if guess > self.high {
+            GuessOutcome::TooBig
+        } else if self.low == self.high {
+            GuessOutcome::Win
+        } else if self.high - guess >= guess - self.low {
+            self.low = guess + 1;
+            GuessOutcome::TooSmall
+        } else {
+            self.high = guess - 1;
+            GuessOutcome::TooBig
+        };
+        self.answers.push((guess, outcome));
+        Ok(outcome)
+    }
+
+    /// Parses a raw line of input and, if it holds a guess, makes it.
+    pub fn take_turn(&mut self, line: &str) -> Turn {
+        let guess = match parse_guess(line) {
+            Ok(guess) => guess,
+            Err(err) => return Turn::Invalid(err),
+        };
+        match self.guess(guess) {
+            Ok(outcome) => Turn::Guessed { guess, outcome },
+            Err(error) => Turn::Rejected { guess, error },
+        }
+    }
+
+    /// The numbers that are still consistent with every answer.
+    pub fn candidates(&self) -> RangeInclusive<u32> {
+        self.low..=self.high
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    /// Every guess so far with the answer it got.
+    pub fn answers(&self) -> &[(u32, GuessOutcome)] {
+        &self.answers
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.answers.len() as u32
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn status(&self) -> Status {
+        if matches!(self.answers.last(), Some((_, GuessOutcome::Win))) {
+            Status::Won
+        } else if self.attempts_left() == Some(0) {
+            Status::Lost
+        } else {
+            Status::Playing
+        }
+    }
+
+    /// The number the host commits to once the game is over. Every number
+    /// left in `candidates` agrees with the answers, so any of them will do.
+    pub fn secret(&self) -> Option<u32> {
+        match self.status() {
+            Status::Playing => None,
+            Status::Won | Status::Lost => Some(self.low),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimal_attempts;
+
+    fn agrees(host: &EvilHost, secret: u32) -> bool {
+        host.answers()
+            .iter()
+            .all(|&(guess, outcome)| GuessOutcome::from(guess.cmp(&secret)) == outcome)
+    }
+
+    #[test]
+    fn bisection_needs_the_worst_case() {
+        let mut host = EvilHost::new(1..=100);
+        let (mut low, mut high) = (1, 100);
+        loop {
+            let guess = low + (high - low) / 2;
+            match host.guess(guess).unwrap() {
+                GuessOutcome::TooSmall => low = guess + 1,
+                GuessOutcome::TooBig => high = guess - 1,
+                GuessOutcome::Win => break,
+            }
+        }
+        assert_eq!(host.attempts(), optimal_attempts(&(1..=100)));
+        assert_eq!(host.status(), Status::Won);
+        assert!(agrees(&host, host.secret().unwrap()));
+    }
+
+    #[test]
+    fn linear_scan_is_made_to_go_all_the_way() {
+        let mut host = EvilHost::new(1..=10);
+        for guess in 1..10 {
+            assert_eq!(host.guess(guess), Ok(GuessOutcome::TooSmall));
+        }
+        assert_eq!(host.guess(10), Ok(GuessOutcome::Win));
+        assert_eq!(host.secret(), Some(10));
+    }
+
+    #[test]
+    fn lost_games_reveal_an_honest_secret() {
+        let mut host = EvilHost::new(1..=100).with_max_attempts(3);
+        for guess in [90, 10, 50] {
+            host.guess(guess).unwrap();
+        }
+        assert_eq!(host.status(), Status::Lost);
+        let secret = host.secret().unwrap();
+        assert!(host.candidates().contains(&secret));
+        assert!(agrees(&host, secret));
+        assert_eq!(host.guess(secret), Err(GuessError::GameOver));
+    }
+
+    #[test]
+    fn guesses_outside_the_candidates_get_the_only_honest_answer() {
+        let mut host = EvilHost::new(1..=100);
+        assert_eq!(host.guess(30), Ok(GuessOutcome::TooSmall));
+        assert_eq!(host.guess(10), Ok(GuessOutcome::TooSmall));
+        assert_eq!(host.candidates(), 31..=100);
+        assert!(matches!(
+            host.take_turn("101"),
+            Turn::Rejected {
+                guess: 101,
+                error: GuessError::OutOfRange { .. }
+            }
+        ));
+        assert_eq!(host.attempts(), 2);
+    }
+}
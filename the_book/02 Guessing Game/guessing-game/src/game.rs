@@ -0,0 +1,430 @@
+use crate::hints::{self, Hint, HintError, HintKind};
+use crate::i18n::{Lang, Localize};
+use crate::input::{ParseGuessError, parse_guess};
+use rand::distributions::Uniform;
+use rand::prng::ChaChaRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// The result of comparing a guess against the secret number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuessOutcome {
+    TooSmall,
+    TooBig,
+    Win,
+}
+
+impl From<Ordering> for GuessOutcome {
+    fn from(ordering: Ordering) -> GuessOutcome {
+        match ordering {
+            Ordering::Less => GuessOutcome::TooSmall,
+            Ordering::Greater => GuessOutcome::TooBig,
+            Ordering::Equal => GuessOutcome::Win,
+        }
+    }
+}
+
+/// Where a game stands after the most recent guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Playing,
+    Won,
+    Lost,
+}
+
+/// Why a guess was rejected without being compared against the secret.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuessError {
+    OutOfRange {
+        guess: u32,
+        range: RangeInclusive<u32>,
+    },
+    GameOver,
+}
+
+impl Localize for GuessError {
+    fn localize(&self, lang: Lang) -> String {
+        match self {
+            GuessError::OutOfRange { guess, range } => lang.format(
+                "out_of_range",
+                &[
+                    ("guess", &lang.number(*guess)),
+                    ("min", &lang.number(*range.start())),
+                    ("max", &lang.number(*range.end())),
+                ],
+            ),
+            GuessError::GameOver => lang.format("game_over", &[]),
+        }
+    }
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+/// What one line of player input did to a game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Turn {
+    /// The line was not a valid guess.
+    Invalid(ParseGuessError),
+    /// The guess was parsed but not compared against the secret.
+    Rejected {
+        guess: u32,
+        error: GuessError,
+    },
+    Guessed {
+        guess: u32,
+        outcome: GuessOutcome,
+    },
+    /// The player asked for a hint with `hint <kind>`.
+    Hint(Result<Hint, HintError>),
+}
+
+/// The fewest guesses that always find a secret in `range`, i.e. the number
+/// of steps a perfect bisection needs.
+pub fn optimal_attempts(range: &RangeInclusive<u32>) -> u32 {
+    let size = u64::from(*range.end() - *range.start()) + 1;
+    64 - size.leading_zeros()
+}
+
+/// Points for a win: harder ranges are worth more, and every attempt left
+/// unused multiplies the reward.
+pub fn score(range: &RangeInclusive<u32>, attempts_left: u32) -> u64 {
+    10 * u64::from(optimal_attempts(range)) * (u64::from(attempts_left) + 1)
+}
+
+/// A single round of the guessing game: the secret, the range it was drawn
+/// from and every guess made so far.
+#[derive(Debug, Clone)]
+pub struct Game {
+    secret: u32,
+    range: RangeInclusive<u32>,
+    max_attempts: Option<u32>,
+    history: Vec<u32>,
+    hints: Vec<HintKind>,
+    hints_used: Vec<HintKind>,
+}
+
+impl Game {
+    /// Starts a game with a known secret.
+    ///
+    /// Panics if `secret` lies outside `range`.
+    pub fn new(secret: u32, range: RangeInclusive<u32>) -> Game {
+        assert!(range.contains(&secret), "secret must lie within the range");
+        Game {
+            secret,
+            range,
+            max_attempts: None,
+            history: Vec::new(),
+            hints: Vec::new(),
+            hints_used: Vec::new(),
+        }
+    }
+
+    /// Offers the given kinds of hint. Games start without any.
+    pub fn with_hints(mut self, hints: &[HintKind]) -> Game {
+        self.hints = hints.to_vec();
+        self
+    }
+
+    /// Limits the game to `max_attempts` guesses, after which it is lost.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Game {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Starts a game with a secret drawn uniformly from `range`.
+    pub fn random(range: RangeInclusive<u32>) -> Game {
+        Game::from_rng(&mut rand::thread_rng(), range)
+    }
+
+    /// Starts a game whose secret is fully determined by `seed`, so a
+    /// session can be reproduced by replaying the same guesses.
+    pub fn seeded(seed: u64, range: RangeInclusive<u32>) -> Game {
+        Game::from_rng(&mut ChaChaRng::seed_from_u64(seed), range)
+    }
+
+    /// Starts a game with a secret drawn uniformly from `range` using `rng`.
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R, range: RangeInclusive<u32>) -> Game {
+        let secret = rng.sample(Uniform::new_inclusive(*range.start(), *range.end()));
+        Game::new(secret, range)
+    }
+
+    /// Compares `guess` with the secret and records it in the history.
+    ///
+    /// Guesses outside the game's range are rejected and not recorded, as is
+    /// any guess made once the game is over.
+    pub fn guess(&mut self, guess: u32) -> Result<GuessOutcome, GuessError> {
+        if self.status() != Status::Playing {
+            return Err(GuessError::GameOver);
+        }
+        if !self.range.contains(&guess) {
+            return Err(GuessError::OutOfRange {
+                guess,
+                range: self.range.clone(),
+            });
+        }
+        self.history.push(guess);
+        Ok(self.compare(guess))
+    }
+
+    /// Compares `guess` with the secret without recording it, which is how
+    /// [`guess`](Game::guess) answers once a guess has been accepted.
+    pub fn compare(&self, guess: u32) -> GuessOutcome {
+        guess.cmp(&self.secret).into()
+    }
+
+    /// Gives a hint and charges its cost against the score.
+    pub fn request_hint(&mut self, kind: HintKind) -> Result<Hint, HintError> {
+        if !self.hints.contains(&kind) {
+            return Err(HintError::Disabled(kind));
+        }
+        let hint = hints::hint(self, kind).ok_or(HintError::Unavailable(kind))?;
+        self.hints_used.push(kind);
+        Ok(hint)
+    }
+
+    /// Charges for hints given before the game was saved.
+    pub(crate) fn restore_hints_used(&mut self, hints_used: &[HintKind]) {
+        self.hints_used = hints_used.to_vec();
+    }
+
+    /// Parses a raw line of input and, if it holds a guess, makes it.
+    /// `hint <kind>` asks for a hint instead.
+    pub fn take_turn(&mut self, line: &str) -> Turn {
+        if let Some(kind) = line.trim().strip_prefix("hint") {
+            return Turn::Hint(kind.parse().and_then(|kind| self.request_hint(kind)));
+        }
+        let guess = match parse_guess(line) {
+            Ok(guess) => guess,
+            Err(err) => return Turn::Invalid(err),
+        };
+        match self.guess(guess) {
+            Ok(outcome) => Turn::Guessed { guess, outcome },
+            Err(error) => Turn::Rejected { guess, error },
+        }
+    }
+
+    pub fn secret(&self) -> u32 {
+        self.secret
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    /// Every guess made so far, oldest first.
+    pub fn history(&self) -> &[u32] {
+        &self.history
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    /// The kinds of hint this game offers.
+    pub fn hints(&self) -> &[HintKind] {
+        &self.hints
+    }
+
+    /// Every hint given so far, oldest first.
+    pub fn hints_used(&self) -> &[HintKind] {
+        &self.hints_used
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    /// How many guesses remain, or `None` if the game has no budget.
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn status(&self) -> Status {
+        if self.history.last() == Some(&self.secret) {
+            Status::Won
+        } else if self.attempts_left() == Some(0) {
+            Status::Lost
+        } else {
+            Status::Playing
+        }
+    }
+
+    /// The score for this game, less the cost of any hints: zero unless it
+    /// has been won.
+    pub fn score(&self) -> u64 {
+        let penalty: u64 = self.hints_used.iter().map(|kind| kind.cost()).sum();
+        match self.status() {
+            Status::Won => {
+                score(&self.range, self.attempts_left().unwrap_or(0)).saturating_sub(penalty)
+            }
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_guess_wins() {
+        let mut game = Game::new(42, 1..=100);
+        assert_eq!(game.guess(42), Ok(GuessOutcome::Win));
+    }
+
+    #[test]
+    fn larger_guess_is_too_big() {
+        let mut game = Game::new(42, 1..=100);
+        assert_eq!(game.guess(43), Ok(GuessOutcome::TooBig));
+        assert_eq!(game.guess(100), Ok(GuessOutcome::TooBig));
+    }
+
+    #[test]
+    fn smaller_guess_is_too_small() {
+        let mut game = Game::new(42, 1..=100);
+        assert_eq!(game.guess(1), Ok(GuessOutcome::TooSmall));
+        assert_eq!(game.guess(41), Ok(GuessOutcome::TooSmall));
+    }
+
+    #[test]
+    fn guesses_are_recorded_in_order() {
+        let mut game = Game::new(42, 1..=100);
+        game.guess(50).unwrap();
+        game.guess(25).unwrap();
+        game.guess(42).unwrap();
+        assert_eq!(game.history(), &[50, 25, 42]);
+        assert_eq!(game.attempts(), 3);
+    }
+
+    #[test]
+    fn out_of_range_guess_is_rejected() {
+        let mut game = Game::new(5, 1..=10);
+        assert_eq!(
+            game.guess(11),
+            Err(GuessError::OutOfRange {
+                guess: 11,
+                range: 1..=10
+            })
+        );
+        assert!(game.guess(0).is_err());
+        assert_eq!(game.attempts(), 0);
+    }
+
+    #[test]
+    fn take_turn_parses_and_guesses() {
+        let mut game = Game::new(42, 1..=100);
+        assert_eq!(
+            game.take_turn("abc\n"),
+            Turn::Invalid(ParseGuessError::NotANumber("abc".into()))
+        );
+        assert!(matches!(
+            game.take_turn("101\n"),
+            Turn::Rejected { guess: 101, .. }
+        ));
+        assert_eq!(
+            game.take_turn(" 42 \n"),
+            Turn::Guessed {
+                guess: 42,
+                outcome: GuessOutcome::Win
+            }
+        );
+        assert_eq!(game.attempts(), 1);
+    }
+
+    #[test]
+    fn hints_must_be_enabled_and_cost_points() {
+        let mut game = Game::new(42, 1..=100)
+            .with_max_attempts(10)
+            .with_hints(&[HintKind::Parity]);
+        assert_eq!(
+            game.take_turn("hint divisor\n"),
+            Turn::Hint(Err(HintError::Disabled(HintKind::Divisor)))
+        );
+        assert_eq!(game.take_turn("hint parity\n"), Turn::Hint(Ok(Hint::Even)));
+        assert!(matches!(
+            game.take_turn("hint please\n"),
+            Turn::Hint(Err(HintError::Unknown(_)))
+        ));
+        assert_eq!(game.attempts(), 0);
+        game.guess(42).unwrap();
+        assert_eq!(game.score(), score(&(1..=100), 9) - HintKind::Parity.cost());
+    }
+
+    #[test]
+    fn running_out_of_attempts_loses() {
+        let mut game = Game::new(42, 1..=100).with_max_attempts(2);
+        assert_eq!(game.attempts_left(), Some(2));
+        game.guess(10).unwrap();
+        assert_eq!(game.attempts_left(), Some(1));
+        assert_eq!(game.status(), Status::Playing);
+        game.guess(90).unwrap();
+        assert_eq!(game.status(), Status::Lost);
+        assert_eq!(game.guess(42), Err(GuessError::GameOver));
+        assert_eq!(game.score(), 0);
+    }
+
+    #[test]
+    fn winning_on_the_last_attempt_counts() {
+        let mut game = Game::new(42, 1..=100).with_max_attempts(2);
+        game.guess(10).unwrap();
+        game.guess(42).unwrap();
+        assert_eq!(game.status(), Status::Won);
+        assert_eq!(game.guess(42), Err(GuessError::GameOver));
+    }
+
+    #[test]
+    fn score_rewards_unused_attempts_and_larger_ranges() {
+        assert_eq!(optimal_attempts(&(1..=1)), 1);
+        assert_eq!(optimal_attempts(&(1..=10)), 4);
+        assert_eq!(optimal_attempts(&(1..=100)), 7);
+        assert_eq!(optimal_attempts(&(1..=u32::MAX)), 32);
+        assert_eq!(optimal_attempts(&(0..=u32::MAX)), 33);
+
+        assert_eq!(score(&(1..=100), 0), 70);
+        assert_eq!(score(&(1..=100), 3), 280);
+        assert!(score(&(1..=10_000), 3) > score(&(1..=100), 3));
+
+        let mut game = Game::new(42, 1..=100).with_max_attempts(10);
+        game.guess(50).unwrap();
+        game.guess(42).unwrap();
+        assert_eq!(game.score(), score(&(1..=100), 8));
+    }
+
+    #[test]
+    fn same_seed_gives_same_secret() {
+        for seed in 0..20 {
+            assert_eq!(
+                Game::seeded(seed, 1..=10_000).secret(),
+                Game::seeded(seed, 1..=10_000).secret()
+            );
+        }
+        let secrets: Vec<u32> = (0..20)
+            .map(|seed| Game::seeded(seed, 1..=10_000).secret())
+            .collect();
+        assert!(secrets.iter().any(|&secret| secret != secrets[0]));
+    }
+
+    #[test]
+    fn random_secret_stays_in_range() {
+        for _ in 0..100 {
+            let game = Game::random(1..=10);
+            assert!(game.range().contains(&game.secret()));
+        }
+        let game = Game::random(1..=u32::MAX);
+        assert!(game.secret() >= 1);
+    }
+}
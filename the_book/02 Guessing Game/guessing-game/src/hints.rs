@@ -0,0 +1,234 @@
+//! Optional clues about the secret beyond `Too big!`/`Too small!`.
+//!
+//! Every clue is computed by [`hint`], a pure function of the game state,
+//! so the same game always gives the same clues.
+
+use crate::Game;
+use crate::i18n::{Lang, Localize};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The kinds of clue a game can offer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HintKind {
+    /// Whether the latest guess is closer to the secret than the one before.
+    Temperature,
+    /// Whether the secret is even or odd.
+    Parity,
+    /// Whether the secret is divisible by the next number in [`DIVISORS`].
+    Divisor,
+}
+
+/// Divisors offered by successive divisibility hints. Two is left out
+/// because parity already covers it.
+pub const DIVISORS: [u32; 8] = [3, 4, 5, 6, 7, 8, 9, 10];
+
+impl HintKind {
+    pub const ALL: [HintKind; 3] = [HintKind::Temperature, HintKind::Parity, HintKind::Divisor];
+
+    /// Points taken off the score each time this hint is asked for.
+    pub fn cost(self) -> u64 {
+        match self {
+            HintKind::Temperature => 0,
+            HintKind::Parity => 20,
+            HintKind::Divisor => 30,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HintKind::Temperature => "warmer",
+            HintKind::Parity => "parity",
+            HintKind::Divisor => "divisor",
+        }
+    }
+}
+
+impl fmt::Display for HintKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for HintKind {
+    type Err = HintError;
+
+    fn from_str(s: &str) -> Result<HintKind, HintError> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "warmer" | "colder" | "temperature" => Ok(HintKind::Temperature),
+            "parity" | "even" | "odd" => Ok(HintKind::Parity),
+            "divisor" | "divisibility" => Ok(HintKind::Divisor),
+            other => Err(HintError::Unknown(other.to_string())),
+        }
+    }
+}
+
+/// A clue about the secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    Warmer,
+    Colder,
+    /// Both guesses are equally far from the secret.
+    Same,
+    Even,
+    Odd,
+    DivisibleBy(u32),
+    NotDivisibleBy(u32),
+}
+
+impl Localize for Hint {
+    fn localize(&self, lang: Lang) -> String {
+        match self {
+            Hint::Warmer => lang.format("warmer", &[]),
+            Hint::Colder => lang.format("colder", &[]),
+            Hint::Same => lang.format("same_distance", &[]),
+            Hint::Even => lang.format("even", &[]),
+            Hint::Odd => lang.format("odd", &[]),
+            Hint::DivisibleBy(d) => lang.format("divisible_by", &[("divisor", d)]),
+            Hint::NotDivisibleBy(d) => lang.format("not_divisible_by", &[("divisor", d)]),
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+/// Why a hint could not be given.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HintError {
+    Unknown(String),
+    /// This difficulty does not offer that kind of hint.
+    Disabled(HintKind),
+    /// There is nothing (more) to tell yet, e.g. warmer/colder before the
+    /// second guess or after every divisor has been used.
+    Unavailable(HintKind),
+}
+
+impl Localize for HintError {
+    fn localize(&self, lang: Lang) -> String {
+        match self {
+            HintError::Unknown(kind) => lang.format("unknown_hint", &[("kind", kind)]),
+            HintError::Disabled(kind) => lang.format("hint_disabled", &[("kind", kind)]),
+            HintError::Unavailable(kind) => lang.format("hint_unavailable", &[("kind", kind)]),
+        }
+    }
+}
+
+impl fmt::Display for HintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for HintError {}
+
+/// The clue of the given kind for the game as it stands, or `None` if there
+/// is nothing to tell. Enabled kinds and costs are the caller's concern.
+pub fn hint(game: &Game, kind: HintKind) -> Option<Hint> {
+    let secret = game.secret();
+    match kind {
+        HintKind::Temperature => {
+            let [.., previous, latest] = game.history() else {
+                return None;
+            };
+            Some(
+                match latest.abs_diff(secret).cmp(&previous.abs_diff(secret)) {
+                    std::cmp::Ordering::Less => Hint::Warmer,
+                    std::cmp::Ordering::Greater => Hint::Colder,
+                    std::cmp::Ordering::Equal => Hint::Same,
+                },
+            )
+        }
+        // Parity never changes, so asking twice tells nothing new.
+        HintKind::Parity if game.hints_used().contains(&HintKind::Parity) => None,
+        HintKind::Parity => Some(if secret.is_multiple_of(2) {
+            Hint::Even
+        } else {
+            Hint::Odd
+        }),
+        HintKind::Divisor => {
+            let used = game
+                .hints_used()
+                .iter()
+                .filter(|&&used| used == HintKind::Divisor)
+                .count();
+            DIVISORS.get(used).map(|&d| {
+                if secret.is_multiple_of(d) {
+                    Hint::DivisibleBy(d)
+                } else {
+                    Hint::NotDivisibleBy(d)
+                }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temperature_compares_the_last_two_guesses() {
+        let mut game = Game::new(50, 1..=100);
+        assert_eq!(hint(&game, HintKind::Temperature), None);
+        game.guess(10).unwrap();
+        assert_eq!(hint(&game, HintKind::Temperature), None);
+        game.guess(40).unwrap();
+        assert_eq!(hint(&game, HintKind::Temperature), Some(Hint::Warmer));
+        game.guess(90).unwrap();
+        assert_eq!(hint(&game, HintKind::Temperature), Some(Hint::Colder));
+        game.guess(10).unwrap();
+        assert_eq!(hint(&game, HintKind::Temperature), Some(Hint::Same));
+    }
+
+    #[test]
+    fn parity_and_divisors_describe_the_secret() {
+        assert_eq!(
+            hint(&Game::new(12, 1..=100), HintKind::Parity),
+            Some(Hint::Even)
+        );
+        assert_eq!(
+            hint(&Game::new(7, 1..=100), HintKind::Parity),
+            Some(Hint::Odd)
+        );
+        assert_eq!(
+            hint(&Game::new(12, 1..=100), HintKind::Divisor),
+            Some(Hint::DivisibleBy(3))
+        );
+        assert_eq!(
+            hint(&Game::new(10, 1..=100), HintKind::Divisor),
+            Some(Hint::NotDivisibleBy(3))
+        );
+    }
+
+    #[test]
+    fn each_divisor_hint_moves_to_the_next_divisor() {
+        let mut game = Game::new(20, 1..=100).with_hints(&[HintKind::Divisor]);
+        let hints: Vec<Hint> = DIVISORS
+            .iter()
+            .map(|_| game.request_hint(HintKind::Divisor).unwrap())
+            .collect();
+        assert_eq!(hints[0], Hint::NotDivisibleBy(3));
+        assert_eq!(hints[1], Hint::DivisibleBy(4));
+        assert_eq!(hints[2], Hint::DivisibleBy(5));
+        assert_eq!(
+            game.request_hint(HintKind::Divisor),
+            Err(HintError::Unavailable(HintKind::Divisor))
+        );
+    }
+
+    #[test]
+    fn parses_kinds() {
+        assert_eq!("Colder".parse(), Ok(HintKind::Temperature));
+        assert_eq!("parity".parse(), Ok(HintKind::Parity));
+        assert_eq!("divisibility".parse(), Ok(HintKind::Divisor));
+        assert!("secret".parse::<HintKind>().is_err());
+    }
+}
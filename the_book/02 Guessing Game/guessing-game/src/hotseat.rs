@@ -0,0 +1,191 @@
+//! Several players at one terminal taking turns to guess the same secret.
+
+use crate::i18n::{Lang, Localize};
+use crate::{Game, Status, Turn};
+use std::fmt;
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 8;
+
+/// Returned when the number of players is outside `MIN_PLAYERS..=MAX_PLAYERS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerCountError(pub usize);
+
+impl Localize for PlayerCountError {
+    fn localize(&self, lang: Lang) -> String {
+        lang.format(
+            "player_count",
+            &[
+                ("min", &MIN_PLAYERS),
+                ("max", &MAX_PLAYERS),
+                ("count", &self.0),
+            ],
+        )
+    }
+}
+
+impl fmt::Display for PlayerCountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for PlayerCountError {}
+
+/// One player and their own view of the shared game.
+#[derive(Debug, Clone)]
+pub struct Seat {
+    pub name: String,
+    pub game: Game,
+}
+
+impl Seat {
+    /// The distance of this player's closest guess from the secret.
+    pub fn closest(&self) -> Option<u32> {
+        let secret = self.game.secret();
+        self.game
+            .history()
+            .iter()
+            .map(|&guess| guess.abs_diff(secret))
+            .min()
+    }
+}
+
+/// Players take turns in order, each with their own attempt budget. The
+/// first to find the secret wins; players who run out sit the rest out.
+#[derive(Debug, Clone)]
+pub struct HotSeat {
+    seats: Vec<Seat>,
+    current: usize,
+}
+
+impl HotSeat {
+    /// Seats every player in front of their own copy of `game`, so they
+    /// share its secret, attempt budget and hints.
+    pub fn new(names: Vec<String>, game: Game) -> Result<HotSeat, PlayerCountError> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&names.len()) {
+            return Err(PlayerCountError(names.len()));
+        }
+        let seats = names
+            .into_iter()
+            .map(|name| Seat {
+                name,
+                game: game.clone(),
+            })
+            .collect();
+        Ok(HotSeat { seats, current: 0 })
+    }
+
+    pub fn seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    /// The index of the player whose turn it is, or `None` once the game
+    /// is over.
+    pub fn current(&self) -> Option<usize> {
+        if self.is_over() {
+            None
+        } else {
+            Some(self.current)
+        }
+    }
+
+    /// The index of the player who found the secret, if anyone has.
+    pub fn winner(&self) -> Option<usize> {
+        self.seats
+            .iter()
+            .position(|seat| seat.game.status() == Status::Won)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.winner().is_some()
+            || self
+                .seats
+                .iter()
+                .all(|seat| seat.game.status() != Status::Playing)
+    }
+
+    /// Plays a line for the current player. The turn only passes on once
+    /// the player has made a guess that counts.
+    pub fn take_turn(&mut self, line: &str) -> Turn {
+        let turn = self.seats[self.current].game.take_turn(line);
+        if let Turn::Guessed { .. } = turn {
+            self.advance();
+        }
+        turn
+    }
+
+    fn advance(&mut self) {
+        if self.is_over() {
+            return;
+        }
+        let players = self.seats.len();
+        for step in 1..=players {
+            let next = (self.current + step) % players;
+            if self.seats[next].game.status() == Status::Playing {
+                self.current = next;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(secret: u32, max: u32, attempts: u32) -> Game {
+        Game::new(secret, 1..=max).with_max_attempts(attempts)
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn player_count_is_checked() {
+        assert_eq!(
+            HotSeat::new(names(&["ana"]), game(5, 10, 3)).unwrap_err(),
+            PlayerCountError(1)
+        );
+        let nine = names(&["a", "b", "c", "d", "e", "f", "g", "h", "i"]);
+        assert!(HotSeat::new(nine, game(5, 10, 3)).is_err());
+    }
+
+    #[test]
+    fn turns_rotate_and_the_first_to_find_it_wins() {
+        let mut hotseat = HotSeat::new(names(&["ana", "bo", "cy"]), game(42, 100, 5)).unwrap();
+        assert_eq!(hotseat.current(), Some(0));
+        hotseat.take_turn("50\n");
+        assert_eq!(hotseat.current(), Some(1));
+        // Invalid input does not cost the player their turn.
+        hotseat.take_turn("fifty\n");
+        assert_eq!(hotseat.current(), Some(1));
+        hotseat.take_turn("40\n");
+        hotseat.take_turn("42\n");
+        assert!(hotseat.is_over());
+        assert_eq!(hotseat.current(), None);
+        assert_eq!(hotseat.winner(), Some(2));
+        let attempts: Vec<u32> = hotseat.seats().iter().map(|s| s.game.attempts()).collect();
+        assert_eq!(attempts, [1, 1, 1]);
+        assert_eq!(hotseat.seats()[0].closest(), Some(8));
+    }
+
+    #[test]
+    fn players_out_of_attempts_are_skipped() {
+        let mut hotseat = HotSeat::new(names(&["ana", "bo"]), game(7, 10, 1)).unwrap();
+        hotseat.take_turn("1\n");
+        assert_eq!(hotseat.current(), Some(1));
+        hotseat.take_turn("2\n");
+        assert!(hotseat.is_over());
+        assert!(hotseat.winner().is_none());
+    }
+
+    #[test]
+    fn players_with_the_same_name_are_told_apart() {
+        let mut hotseat = HotSeat::new(names(&["ana", "ana"]), game(7, 10, 2)).unwrap();
+        hotseat.take_turn("1\n");
+        hotseat.take_turn("7\n");
+        assert_eq!(hotseat.winner(), Some(1));
+    }
+}
@@ -0,0 +1,1770 @@
+//! Translations of the messages the game prints, and locale-aware number
+//! formatting.
+//!
+//! Each language has a catalog of `(key, template)` pairs. Templates name
+//! their arguments in braces, e.g. `{guess}`, so translations are free to
+//! put them in whatever order reads naturally.
+
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lang {
+    #[default]
+    En,
+    Hi,
+    Es,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 3] = [Lang::En, Lang::Hi, Lang::Es];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Hi => "hi",
+            Lang::Es => "es",
+        }
+    }
+
+    /// The language of the first locale variable that is set, checked in
+    /// POSIX order: `LC_ALL`, then `LC_MESSAGES`, then `LANG`. Unknown or
+    /// missing locales fall back to English.
+    pub fn from_env() -> Lang {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Lang::En => EN,
+            Lang::Hi => HI,
+            Lang::Es => ES,
+        }
+    }
+
+    /// The template for `key`, falling back to English and then to the key
+    /// itself so that a gap in a catalog never hides a message entirely.
+    pub fn template(self, key: &str) -> &str {
+        let find = |lang: Lang| {
+            lang.catalog()
+                .iter()
+                .find(|(known, _)| *known == key)
+                .map(|(_, template)| *template)
+        };
+        find(self).or_else(|| find(Lang::En)).unwrap_or(key)
+    }
+
+    /// Fills in the `{name}` placeholders of `key`'s template.
+    pub fn format(self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        args.iter()
+            .fold(self.template(key).to_string(), |message, (name, value)| {
+                message.replace(&format!("{{{}}}", name), &value.to_string())
+            })
+    }
+
+    /// Formats a whole number with the locale's digit grouping: `12,345` in
+    /// English, `12.345` in Spanish (which leaves four-digit numbers alone)
+    /// and `1,23,45,678` in Hindi, which groups by two above the thousands.
+    pub fn number(self, n: impl Into<u64>) -> String {
+        let digits = n.into().to_string();
+        let (separator, min_len) = match self {
+            Lang::En | Lang::Hi => (',', 4),
+            Lang::Es => ('.', 5),
+        };
+        if digits.len() < min_len {
+            return digits;
+        }
+        let (head, last_three) = digits.split_at(digits.len() - 3);
+        let group = if self == Lang::Hi { 2 } else { 3 };
+        let mut groups: Vec<&str> = Vec::new();
+        let mut rest = head;
+        while rest.len() > group {
+            let (front, back) = rest.split_at(rest.len() - group);
+            groups.push(back);
+            rest = front;
+        }
+        groups.push(rest);
+        groups.reverse();
+        groups.push(last_three);
+        groups.join(&separator.to_string())
+    }
+}
+
+/// A value whose wording lives in the catalogs, so it can be shown in any
+/// language. Types that implement it display their English text.
+pub trait Localize {
+    fn localize(&self, lang: Lang) -> String;
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// The language was not one we have a catalog for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLangError(pub String);
+
+impl Localize for ParseLangError {
+    fn localize(&self, lang: Lang) -> String {
+        let codes: Vec<&str> = Lang::ALL.iter().map(|lang| lang.code()).collect();
+        lang.format(
+            "unknown_language",
+            &[("name", &self.0), ("expected", &codes.join(", "))],
+        )
+    }
+}
+
+impl fmt::Display for ParseLangError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for ParseLangError {}
+
+impl FromStr for Lang {
+    type Err = ParseLangError;
+
+    /// Accepts a bare code (`es`) or a full locale name (`es_MX.UTF-8`).
+    fn from_str(s: &str) -> Result<Lang, ParseLangError> {
+        let code = s.split(['_', '-', '.', '@']).next().unwrap_or_default();
+        match code.to_ascii_lowercase().as_str() {
+            "en" | "c" | "posix" => Ok(Lang::En),
+            "hi" => Ok(Lang::Hi),
+            "es" => Ok(Lang::Es),
+            _ => Err(ParseLangError(s.to_string())),
+        }
+    }
+}
+
+const EN: &[(&str, &str)] = &[
+    ("guess_the_number", "Guess the number!"),
+    ("whats_your_name", "What's your name?"),
+    ("secret_is", "The secret number is: {secret}"),
+    (
+        "please_guess",
+        "Please input your guess between {min} and {max}. You have {attempts} attempts.",
+    ),
+    ("hints_on_offer", "Hints on offer: {hints}."),
+    ("hint_cost", "`hint {kind}` (-{cost} points)"),
+    ("you_guessed", "You guessed: {guess}"),
+    ("too_big", "Too big!"),
+    ("too_small", "Too small!"),
+    ("one_try_left", "1 try left."),
+    ("tries_left", "{left} tries left."),
+    ("you_win", "You win!"),
+    ("score", "Score: {score}"),
+    ("you_lose", "You lose, the number was {secret}."),
+    ("cannot_save", "This game cannot be saved."),
+    (
+        "game_saved",
+        "Game saved to {path}. Continue it with --resume {path}",
+    ),
+    ("could_not_save", "Could not save to {path}: {error}"),
+    (
+        "welcome_back",
+        "Welcome back, {player}! {time} played so far.",
+    ),
+    ("was_too_big", "You guessed {guess}: too big."),
+    ("was_too_small", "You guessed {guess}: too small."),
+    ("rating", "Rating: {rating} ({change})."),
+    ("unlocked", "🏆 {player} unlocked {name}: {description}"),
+    ("duration", "{minutes}m {seconds}s"),
+    ("seconds", "{seconds}s"),
+    ("empty_guess", "Please type a number."),
+    ("not_a_number", "`{input}` is not a number."),
+    (
+        "negative",
+        "`{input}` is negative, guesses must be positive.",
+    ),
+    (
+        "too_large",
+        "`{input}` is too large, the largest possible guess is {max}.",
+    ),
+    ("input_closed", "input closed before the game finished"),
+    ("read_failed", "failed to read input: {error}"),
+    (
+        "out_of_range",
+        "{guess} is out of range, the secret is between {min} and {max}",
+    ),
+    ("game_over", "the game is already over"),
+    ("warmer", "Warmer."),
+    ("colder", "Colder."),
+    ("same_distance", "Neither warmer nor colder."),
+    ("even", "The number is even."),
+    ("odd", "The number is odd."),
+    ("divisible_by", "The number is divisible by {divisor}."),
+    (
+        "not_divisible_by",
+        "The number is not divisible by {divisor}.",
+    ),
+    (
+        "unknown_hint",
+        "There is no `{kind}` hint, try warmer, parity or divisor.",
+    ),
+    (
+        "hint_disabled",
+        "The {kind} hint is not available at this difficulty.",
+    ),
+    ("hint_unavailable", "There is no {kind} hint to give yet."),
+    ("only_possible", "Only {number} is still possible."),
+    (
+        "still_possible",
+        "Still possible: {low} to {high} ({count} numbers).",
+    ),
+    (
+        "bits_gained",
+        "This guess gained {bits} bits, a bisection is sure of {ideal}.",
+    ),
+    ("efficiency_so_far", "Efficiency so far: {percent}%."),
+    ("efficiency_overall", "Overall efficiency: {percent}%."),
+    ("clock_running", "The clock is running!"),
+    (
+        "clock_running_limit",
+        "The clock is running! Win within {limit}.",
+    ),
+    ("times_up", "Time's up!"),
+    ("number_was", "The number was {secret}."),
+    (
+        "speedrun_time",
+        "Time: {time} (par {par}). Speedrun score: {score}",
+    ),
+    ("guess_times", "Guess times: {times} (average {average})"),
+    ("new_personal_best", "New personal best!"),
+    (
+        "personal_best",
+        "Personal best on {difficulty}: {score} in {time}.",
+    ),
+    (
+        "already_played",
+        "You have already played puzzle #{number} today. Come back tomorrow!",
+    ),
+    ("daily_challenge", "Daily challenge #{number} for {date}."),
+    ("share_result", "Share your result:"),
+    ("player_name", "Player {number}, what's your name?"),
+    (
+        "name_taken",
+        "{name} is already playing, please pick another name.",
+    ),
+    (
+        "everyone_guess",
+        "Guess a number between {min} and {max}. Everyone has {attempts} attempts.",
+    ),
+    ("your_guess", "{player}, your guess:"),
+    ("winner", "Winner:"),
+    ("player_found", "{winner} {player} found {secret}!"),
+    ("nobody_found", "Nobody found it, the number was {secret}."),
+    ("column_player", "Player"),
+    ("column_attempts", "Attempts"),
+    ("column_closest", "Closest"),
+    ("column_result", "Result"),
+    ("seat_won", "won"),
+    ("seat_out", "out of attempts"),
+    ("seat_playing", "still guessing"),
+    (
+        "replaying",
+        "Replaying {path} (seed {seed}, {min} to {max}).",
+    ),
+    ("replay_matches", "The replay matches the recording."),
+    (
+        "replay_saved",
+        "The recording ends where the player typed :save, before the game was over.",
+    ),
+    (
+        "replay_diverged",
+        "line {line} no longer matches the recording: expected {expected}, got {got}",
+    ),
+    (
+        "think_of_number",
+        "Think of a number between {min} and {max} and I will guess it.",
+    ),
+    (
+        "answer_with",
+        "Answer each guess with higher, lower or correct.",
+    ),
+    ("is_it", "Is it {guess}?"),
+    ("please_answer", "Please answer higher, lower or correct."),
+    ("cheating", "You're cheating: {contradiction}."),
+    ("got_it", "Got it!"),
+    (
+        "your_number_is",
+        "Your number is {number}, found in {guesses} guesses.",
+    ),
+    ("answer_higher", "higher than {guess}"),
+    ("answer_lower", "lower than {guess}"),
+    ("answer_exactly", "exactly {guess}"),
+    (
+        "contradicts",
+        "answer #{number} ({answer}) contradicts answer #{earlier} ({earlier_answer})",
+    ),
+    (
+        "outside_range",
+        "answer #{number} ({answer}) is outside the range you picked from",
+    ),
+    (
+        "not_picked",
+        "I haven't picked a number yet, so guess well.",
+    ),
+    ("cornered", "You cornered me in {guesses} guesses."),
+    ("one_number", "1 number"),
+    ("numbers", "{count} numbers"),
+    (
+        "one_try_left_possible",
+        "1 try left, {possible} still possible.",
+    ),
+    (
+        "tries_left_possible",
+        "{left} tries left, {possible} still possible.",
+    ),
+    ("still_in_play", "{possible} still possible."),
+    ("may_lie_once", "Careful: I may lie in 1 of my answers."),
+    (
+        "may_lie",
+        "Careful: I may lie in up to {lies} of my answers.",
+    ),
+    ("my_answers", "My answers were:"),
+    ("a_lie", "(a lie)"),
+    ("secret_code", "The secret code is: {code}"),
+    (
+        "crack_the_code",
+        "Crack the code: {rules}. You have {attempts} attempts.",
+    ),
+    (
+        "bulls_and_cows",
+        "A bull is a right digit in the right place, a cow a right digit in the wrong place. Type `hint` for a suggestion.",
+    ),
+    (
+        "code_rules_repeats",
+        "{length} digits from 0 to {highest}, repeats allowed",
+    ),
+    (
+        "code_rules_unique",
+        "{length} digits from 0 to {highest}, no repeats",
+    ),
+    ("empty_code", "Please type a code."),
+    ("code_length", "The code has {expected} digits, not {got}."),
+    ("not_a_digit", "`{digit}` is not one of the digits."),
+    (
+        "repeated_digit",
+        "{digit} appears more than once, repeats are off.",
+    ),
+    ("bulls_cows", "{bulls}, {cows}"),
+    ("one_bull", "1 bull"),
+    ("bulls", "{count} bulls"),
+    ("one_cow", "1 cow"),
+    ("cows", "{count} cows"),
+    ("one_code_possible", "1 code is still possible. Try {code}."),
+    (
+        "codes_possible",
+        "{count} codes are still possible. Try {code}.",
+    ),
+    ("cracked_in", "Cracked in {guesses} guesses."),
+    ("code_was", "You lose, the code was {code}."),
+    ("feedback_and_tries", "{feedback}. {tries}"),
+    (
+        "solver_cracks",
+        "The solver cracks this code in {guesses} guesses.",
+    ),
+    ("secret_word", "The secret word is: {word}"),
+    (
+        "guess_the_word",
+        "Guess the {length}-letter word. You have {attempts} attempts.",
+    ),
+    (
+        "hard_mode",
+        "Hard mode: every guess must keep the letters revealed so far.",
+    ),
+    ("found_in", "Found in {guesses} guesses."),
+    ("word_was", "You lose, the word was {word}."),
+    ("empty_word", "Please type a word."),
+    ("word_length", "Words have {length} letters, not {got}."),
+    ("not_a_letter", "`{letter}` is not a letter."),
+    ("not_a_word", "`{word}` is not in the word list."),
+    (
+        "letter_moved",
+        "Hard mode: letter {position} must be {letter}.",
+    ),
+    (
+        "letter_missing",
+        "Hard mode: the guess must contain {letter}.",
+    ),
+    (
+        "hosting",
+        "Hosting on {addr}, the secret is between {min} and {max}.",
+    ),
+    (
+        "joined_as",
+        "Joined as {name}. Guess a number between {min} and {max}.",
+    ),
+    ("secret_between", "The secret is between {min} and {max}."),
+    ("player_joined", "{name} joined the game."),
+    ("player_guessed", "{name} guessed {guess}: {hint}"),
+    ("winner_announced", "Winner!"),
+    (
+        "player_found_in",
+        "{name} found {guess} in {attempts} guesses.",
+    ),
+    ("match_rating", "{player} rating {rating} ({change})"),
+    ("no_rated_games_for", "No rated games for {player} yet."),
+    ("no_rated_games", "No rated games yet."),
+    ("column_rating", "Rating"),
+    ("column_peak", "Peak"),
+    ("column_games", "Games"),
+    ("error", "error: {message}"),
+    ("warning", "warning: {message}"),
+    (
+        "theme_failed",
+        "could not load the theme {name}: {error} (built-in themes: {themes})",
+    ),
+    ("replay_failed", "could not replay {path}: {error}"),
+    (
+        "batch_unreadable",
+        "{failures} of the games could not be read",
+    ),
+    ("batch_failed", "could not run the batch: {error}"),
+    ("resume_failed", "could not resume {path}: {error}"),
+    (
+        "no_data_dir",
+        "no data directory found, the result was not saved",
+    ),
+    (
+        "no_data_dir_daily",
+        "no data directory found, the result will not be saved",
+    ),
+    (
+        "result_not_saved",
+        "could not save the result to {path}: {error}",
+    ),
+    (
+        "daily_check_failed",
+        "could not check today's results: {error}",
+    ),
+    (
+        "achievements_not_saved",
+        "could not save achievements to {path}: {error}",
+    ),
+    (
+        "rating_not_saved",
+        "could not save the rating to {path}: {error}",
+    ),
+    (
+        "ratings_not_saved",
+        "could not save the ratings to {path}: {error}",
+    ),
+    ("ratings_unreadable", "could not read the ratings: {error}"),
+    (
+        "leaderboard_unreadable",
+        "could not read the leaderboard: {error}",
+    ),
+    ("gave_up", "{strategy} gave up: {error}"),
+    ("strategy_failed", "{strategy}: {error}"),
+    (
+        "inconsistent_feedback",
+        "game with seed {seed} answered {outcome} to guess {guess} but the secret was {secret}",
+    ),
+    ("bench_inconsistent", "inconsistent feedback: {error}"),
+    (
+        "not_solved",
+        "game with seed {seed} was not solved within {guesses} guesses",
+    ),
+    (
+        "player_count",
+        "hot-seat games need {min} to {max} players, not {count}",
+    ),
+    (
+        "unknown_difficulty",
+        "unknown difficulty `{name}` (expected easy, normal, hard or insane)",
+    ),
+    (
+        "unknown_color",
+        "unknown color setting `{value}` (expected auto, always or never)",
+    ),
+    (
+        "unknown_language",
+        "unknown language `{name}` (expected {expected})",
+    ),
+    ("save_corrupt", "the save file is corrupted: {reason}"),
+    (
+        "save_version",
+        "the save file is corrupted: unsupported version {version}",
+    ),
+    (
+        "save_tampered",
+        "the save file has been modified since it was saved",
+    ),
+    (
+        "save_invalid",
+        "the save file is not a valid game: {reason}",
+    ),
+    ("secret_unreadable", "the secret is unreadable"),
+    ("secret_outside_range", "the secret is outside the range"),
+    ("impossible_guess", "a guess could not have been made"),
+    ("achievement_first_try", "Mind Reader"),
+    (
+        "achievement_first_try_about",
+        "Win on the very first guess.",
+    ),
+    ("achievement_perfect_bisection", "Binary Searcher"),
+    (
+        "achievement_perfect_bisection_about",
+        "Win by always guessing the middle of the numbers still possible.",
+    ),
+    ("achievement_photo_finish", "Photo Finish"),
+    (
+        "achievement_photo_finish_about",
+        "Win on the last allowed attempt.",
+    ),
+    ("achievement_hot_streak", "On Fire"),
+    (
+        "achievement_hot_streak_about",
+        "Win 10 games in a row at one difficulty.",
+    ),
+    ("achievement_centurion", "Centurion"),
+    (
+        "achievement_centurion_about",
+        "Play 100 games at one difficulty.",
+    ),
+    ("no_games_recorded", "No games recorded yet."),
+    ("column_difficulty", "Difficulty"),
+    ("column_played", "Played"),
+    ("column_wins", "Wins"),
+    ("column_best", "Best"),
+    ("column_average", "Average"),
+    ("column_achievements", "Achievements"),
+    ("column_speedrunner", "Speedrunner"),
+    ("column_score", "Score"),
+    ("column_time", "Time"),
+    ("column_tries", "Tries"),
+    ("column_strategy", "Strategy"),
+    ("column_mean", "Mean"),
+    ("column_median", "Median"),
+    ("column_worst", "Worst"),
+    ("missing_value", "{flag} needs a value"),
+    (
+        "invalid_number",
+        "{flag} expects a whole number, got `{value}`",
+    ),
+    (
+        "empty_range",
+        "the range {min}..={max} is empty, --min must be below --max",
+    ),
+    ("no_attempts", "--attempts must be at least 1"),
+    ("no_time", "--time-limit must be at least 1 second"),
+    ("missing_file", "replay needs a session log to play"),
+    (
+        "invalid_speed",
+        "--speed expects a non-negative number, got `{value}`",
+    ),
+    (
+        "unknown_strategy",
+        "unknown strategy `{name}` (expected {expected})",
+    ),
+    (
+        "too_many_lies",
+        "--lies allows at most {max} lies, got {lies}",
+    ),
+    ("conflict", "{first} cannot be combined with {second}"),
+    ("impossible_code", "there is no code of {rules}"),
+    ("unexpected_argument", "unexpected argument `{arg}`"),
+    (
+        "usage",
+        "\
+Usage: guessing-game [OPTIONS]
+       guessing-game --stats
+       guessing-game --ratings [--name <NAME>]
+       guessing-game --serve <ADDR> | --connect <ADDR>
+       guessing-game --bench <GAMES> [--strategy <NAME>]... [--csv]
+       guessing-game replay <FILE> [--speed <FACTOR>]
+       guessing-game batch [FILE]
+
+Every form also takes --color and --theme.
+
+Options:
+  -d, --difficulty <NAME>  easy (1-10), normal (1-100), hard (1-10000)
+                           or insane (1-4294967295) [default: normal]
+      --min <N>            Lowest possible secret, overrides the preset
+      --max <N>            Highest possible secret, overrides the preset
+      --attempts <N>       Attempt budget [default: the preset's budget]
+      --hints              Offer the difficulty's hints: warmer/colder after
+                           each guess, `hint parity` and `hint divisor`
+      --insight            After each guess, show the numbers still possible
+                           and the bits of information it gained
+      --daily              Play today's puzzle, the same for everyone on the
+                           same UTC day; once per day
+      --speedrun           Race the clock: every guess is timed and the score
+                           rewards speed; personal bests are kept
+      --time-limit <SECS>  End a speedrun as lost once SECS seconds pass
+      --seed <N>           Seed the secret so a session can be reproduced
+      --reveal             Print the secret number (alias: --debug)
+      --players <N>        Hot-seat game for 2 to 8 players at one terminal
+      --reverse            You pick the number and the computer guesses it
+      --evil               The host picks no secret and dodges every guess
+                           for as long as it honestly can
+      --code               Crack a code of digits, scored in bulls and cows
+      --length <N>         Digits in the code, up to 6 [default: the preset's]
+      --repeats            Allow a digit to appear more than once in the code
+      --no-repeats         Forbid repeated digits in the code
+      --word               Guess a hidden five-letter word instead
+      --hard-mode          In word mode, every guess must keep the letters
+                           revealed so far
+      --lies <K>           The host may lie in up to K (at most 5) of its
+                           higher/lower answers
+      --resume <FILE>      Continue a game saved by typing `:save [FILE]`
+      --record <FILE>      Log the session so it can be replayed later
+      --speed <FACTOR>     Replay speed-up, 0 for no delays [default: 1]
+      --lang <LANG>        en, hi or es [default: from LC_ALL, LC_MESSAGES
+                           or LANG]
+      --name <NAME>        Player name for the leaderboard, skips the prompt
+      --stats              Print the leaderboard and exit
+      --ratings            Print everyone's skill rating with a chart of its
+                           history, or only the player given by --name
+      --serve <ADDR>       Host a multiplayer game on ADDR, e.g. 0.0.0.0:7878
+      --connect <ADDR>     Join a multiplayer game hosted at ADDR
+      --bench <GAMES>      Play GAMES seeded games per strategy and report
+                           mean, median and worst attempts
+      --strategy <NAME>    linear, random, bisection or biased; repeat to
+                           compare several [default: all]
+      --csv                Print benchmark results as CSV
+      --color <WHEN>       auto, always or never [default: auto, which colors
+                           a terminal unless NO_COLOR is set, and pipes too
+                           if CLICOLOR_FORCE is]
+      --theme <THEME>      default, colorblind, or a JSON theme file
+                           [default: theme.json in the data directory, if any]
+  -h, --help               Print this help
+
+Batch mode reads scripted games as JSON Lines from FILE, or stdin if FILE
+is missing or `-`, and prints one JSON report per game.",
+    ),
+];
+
+const HI: &[(&str, &str)] = &[
+    ("guess_the_number", "संख्या का अनुमान लगाइए!"),
+    ("whats_your_name", "आपका नाम क्या है?"),
+    ("secret_is", "गुप्त संख्या है: {secret}"),
+    (
+        "please_guess",
+        "{min} और {max} के बीच अपना अनुमान लिखिए। आपके पास {attempts} प्रयास हैं।",
+    ),
+    ("hints_on_offer", "उपलब्ध संकेत: {hints}।"),
+    ("hint_cost", "`hint {kind}` (-{cost} अंक)"),
+    ("you_guessed", "आपका अनुमान: {guess}"),
+    ("too_big", "बहुत बड़ा!"),
+    ("too_small", "बहुत छोटा!"),
+    ("one_try_left", "1 प्रयास बाकी है।"),
+    ("tries_left", "{left} प्रयास बाकी हैं।"),
+    ("you_win", "आप जीत गए!"),
+    ("score", "अंक: {score}"),
+    ("you_lose", "आप हार गए, संख्या {secret} थी।"),
+    ("cannot_save", "यह खेल सहेजा नहीं जा सकता।"),
+    (
+        "game_saved",
+        "खेल {path} में सहेजा गया। इसे --resume {path} से जारी रखें",
+    ),
+    ("could_not_save", "{path} में सहेजा नहीं जा सका: {error}"),
+    (
+        "welcome_back",
+        "फिर से स्वागत है, {player}! अब तक {time} खेला गया।",
+    ),
+    ("was_too_big", "आपका अनुमान {guess} था: बहुत बड़ा।"),
+    ("was_too_small", "आपका अनुमान {guess} था: बहुत छोटा।"),
+    ("rating", "रेटिंग: {rating} ({change})।"),
+    ("unlocked", "🏆 {player} ने {name} हासिल किया: {description}"),
+    ("duration", "{minutes} मि {seconds} से"),
+    ("seconds", "{seconds} से"),
+    ("empty_guess", "कृपया एक संख्या लिखिए।"),
+    ("not_a_number", "`{input}` संख्या नहीं है।"),
+    ("negative", "`{input}` ऋणात्मक है, अनुमान धनात्मक होने चाहिए।"),
+    (
+        "too_large",
+        "`{input}` बहुत बड़ा है, सबसे बड़ा संभव अनुमान {max} है।",
+    ),
+    ("input_closed", "खेल खत्म होने से पहले इनपुट बंद हो गया"),
+    ("read_failed", "इनपुट पढ़ा नहीं जा सका: {error}"),
+    (
+        "out_of_range",
+        "{guess} सीमा से बाहर है, गुप्त संख्या {min} और {max} के बीच है",
+    ),
+    ("game_over", "खेल पहले ही खत्म हो चुका है"),
+    ("warmer", "गरम।"),
+    ("colder", "ठंडा।"),
+    ("same_distance", "न गरम, न ठंडा।"),
+    ("even", "संख्या सम है।"),
+    ("odd", "संख्या विषम है।"),
+    ("divisible_by", "संख्या {divisor} से विभाज्य है।"),
+    ("not_divisible_by", "संख्या {divisor} से विभाज्य नहीं है।"),
+    (
+        "unknown_hint",
+        "`{kind}` नाम का कोई संकेत नहीं है, warmer, parity या divisor आज़माइए।",
+    ),
+    ("hint_disabled", "इस कठिनाई पर {kind} संकेत उपलब्ध नहीं है।"),
+    ("hint_unavailable", "अभी देने के लिए कोई {kind} संकेत नहीं है।"),
+    ("only_possible", "अब केवल {number} संभव है।"),
+    (
+        "still_possible",
+        "अभी भी संभव: {low} से {high} ({count} संख्याएँ)।",
+    ),
+    (
+        "bits_gained",
+        "इस अनुमान से {bits} बिट मिले, द्विभाजन से निश्चित रूप से {ideal} मिलते।",
+    ),
+    ("efficiency_so_far", "अब तक की दक्षता: {percent}%।"),
+    ("efficiency_overall", "कुल दक्षता: {percent}%।"),
+    ("clock_running", "घड़ी चल रही है!"),
+    ("clock_running_limit", "घड़ी चल रही है! {limit} के भीतर जीतिए।"),
+    ("times_up", "समय समाप्त!"),
+    ("number_was", "संख्या {secret} थी।"),
+    (
+        "speedrun_time",
+        "समय: {time} (मानक {par})। स्पीडरन अंक: {score}",
+    ),
+    ("guess_times", "अनुमान के समय: {times} (औसत {average})"),
+    ("new_personal_best", "नया व्यक्तिगत रिकॉर्ड!"),
+    (
+        "personal_best",
+        "{difficulty} पर व्यक्तिगत रिकॉर्ड: {time} में {score}।",
+    ),
+    (
+        "already_played",
+        "आप आज पहेली #{number} खेल चुके हैं। कल फिर आइए!",
+    ),
+    ("daily_challenge", "{date} की दैनिक चुनौती #{number}।"),
+    ("share_result", "अपना परिणाम साझा कीजिए:"),
+    ("player_name", "खिलाड़ी {number}, आपका नाम क्या है?"),
+    (
+        "name_taken",
+        "{name} नाम पहले से लिया जा चुका है, कृपया कोई दूसरा नाम चुनिए।",
+    ),
+    (
+        "everyone_guess",
+        "{min} और {max} के बीच एक संख्या का अनुमान लगाइए। सबके पास {attempts} प्रयास हैं।",
+    ),
+    ("your_guess", "{player}, आपका अनुमान:"),
+    ("winner", "विजेता:"),
+    ("player_found", "{winner} {player} ने {secret} ढूँढ लिया!"),
+    ("nobody_found", "कोई नहीं ढूँढ पाया, संख्या {secret} थी।"),
+    ("column_player", "खिलाड़ी"),
+    ("column_attempts", "प्रयास"),
+    ("column_closest", "निकटतम"),
+    ("column_result", "परिणाम"),
+    ("seat_won", "जीते"),
+    ("seat_out", "प्रयास खत्म"),
+    ("seat_playing", "अभी अनुमान लगा रहे"),
+    (
+        "replaying",
+        "{path} दोबारा चल रहा है (बीज {seed}, {min} से {max})।",
+    ),
+    (
+        "replay_matches",
+        "दोबारा चलाया गया खेल रिकॉर्डिंग से मेल खाता है।",
+    ),
+    (
+        "replay_saved",
+        "रिकॉर्डिंग वहीं रुकती है जहाँ खिलाड़ी ने :save लिखा था, खेल ख़त्म होने से पहले।",
+    ),
+    (
+        "replay_diverged",
+        "पंक्ति {line} अब रिकॉर्डिंग से मेल नहीं खाती: अपेक्षित {expected}, मिला {got}",
+    ),
+    (
+        "think_of_number",
+        "{min} और {max} के बीच कोई संख्या सोचिए, मैं उसका अनुमान लगाऊँगा।",
+    ),
+    (
+        "answer_with",
+        "हर अनुमान का उत्तर higher (बड़ी), lower (छोटी) या correct (सही) से दीजिए।",
+    ),
+    ("is_it", "क्या यह {guess} है?"),
+    ("please_answer", "कृपया higher, lower या correct लिखिए।"),
+    ("cheating", "आप धोखा दे रहे हैं: {contradiction}।"),
+    ("got_it", "मिल गई!"),
+    (
+        "your_number_is",
+        "आपकी संख्या {number} है, {guesses} अनुमानों में मिली।",
+    ),
+    ("answer_higher", "{guess} से बड़ी"),
+    ("answer_lower", "{guess} से छोटी"),
+    ("answer_exactly", "ठीक {guess}"),
+    (
+        "contradicts",
+        "उत्तर #{number} ({answer}) उत्तर #{earlier} ({earlier_answer}) के विपरीत है",
+    ),
+    (
+        "outside_range",
+        "उत्तर #{number} ({answer}) आपकी चुनी हुई सीमा से बाहर है",
+    ),
+    (
+        "not_picked",
+        "मैंने अभी कोई संख्या नहीं चुनी है, इसलिए सोच-समझकर अनुमान लगाइए।",
+    ),
+    ("cornered", "आपने मुझे {guesses} अनुमानों में घेर लिया।"),
+    ("one_number", "1 संख्या"),
+    ("numbers", "{count} संख्याएँ"),
+    (
+        "one_try_left_possible",
+        "1 प्रयास बाकी, अभी भी {possible} संभव।",
+    ),
+    (
+        "tries_left_possible",
+        "{left} प्रयास बाकी, अभी भी {possible} संभव।",
+    ),
+    ("still_in_play", "अभी भी {possible} संभव।"),
+    ("may_lie_once", "सावधान: मैं अपने 1 उत्तर में झूठ बोल सकता हूँ।"),
+    (
+        "may_lie",
+        "सावधान: मैं अपने अधिकतम {lies} उत्तरों में झूठ बोल सकता हूँ।",
+    ),
+    ("my_answers", "मेरे उत्तर थे:"),
+    ("a_lie", "(झूठ)"),
+    ("secret_code", "गुप्त कोड है: {code}"),
+    (
+        "crack_the_code",
+        "कोड तोड़िए: {rules}। आपके पास {attempts} प्रयास हैं।",
+    ),
+    (
+        "bulls_and_cows",
+        "बुल सही जगह पर सही अंक है, काउ गलत जगह पर सही अंक। सुझाव के लिए `hint` लिखिए।",
+    ),
+    (
+        "code_rules_repeats",
+        "0 से {highest} तक के {length} अंक, दोहराव की अनुमति है",
+    ),
+    (
+        "code_rules_unique",
+        "0 से {highest} तक के {length} अंक, दोहराव नहीं",
+    ),
+    ("empty_code", "कृपया एक कोड लिखिए।"),
+    ("code_length", "कोड में {expected} अंक हैं, {got} नहीं।"),
+    ("not_a_digit", "`{digit}` मान्य अंकों में से नहीं है।"),
+    ("repeated_digit", "{digit} एक से अधिक बार है, दोहराव बंद है।"),
+    ("bulls_cows", "{bulls}, {cows}"),
+    ("one_bull", "1 बुल"),
+    ("bulls", "{count} बुल"),
+    ("one_cow", "1 काउ"),
+    ("cows", "{count} काउ"),
+    ("one_code_possible", "अभी 1 कोड संभव है। {code} आज़माइए।"),
+    ("codes_possible", "अभी {count} कोड संभव हैं। {code} आज़माइए।"),
+    ("cracked_in", "{guesses} अनुमानों में तोड़ा।"),
+    ("code_was", "आप हार गए, कोड {code} था।"),
+    ("feedback_and_tries", "{feedback}। {tries}"),
+    ("solver_cracks", "सॉल्वर यह कोड {guesses} अनुमानों में तोड़ता है।"),
+    ("secret_word", "गुप्त शब्द है: {word}"),
+    (
+        "guess_the_word",
+        "{length} अक्षरों वाले शब्द का अनुमान लगाइए। आपके पास {attempts} प्रयास हैं।",
+    ),
+    ("hard_mode", "कठिन मोड: हर अनुमान में अब तक मिले अक्षर रखने होंगे।"),
+    ("found_in", "{guesses} अनुमानों में मिला।"),
+    ("word_was", "आप हार गए, शब्द {word} था।"),
+    ("empty_word", "कृपया एक शब्द लिखिए।"),
+    ("word_length", "शब्दों में {length} अक्षर होते हैं, {got} नहीं।"),
+    ("not_a_letter", "`{letter}` अक्षर नहीं है।"),
+    ("not_a_word", "`{word}` शब्द सूची में नहीं है।"),
+    (
+        "letter_moved",
+        "कठिन मोड: अक्षर {position} {letter} होना चाहिए।",
+    ),
+    ("letter_missing", "कठिन मोड: अनुमान में {letter} होना चाहिए।"),
+    (
+        "hosting",
+        "{addr} पर मेज़बानी, गुप्त संख्या {min} और {max} के बीच है।",
+    ),
+    (
+        "joined_as",
+        "{name} के रूप में जुड़े। {min} और {max} के बीच एक संख्या का अनुमान लगाइए।",
+    ),
+    ("secret_between", "गुप्त संख्या {min} और {max} के बीच है।"),
+    ("player_joined", "{name} खेल में शामिल हुए।"),
+    ("player_guessed", "{name} का अनुमान {guess}: {hint}"),
+    ("winner_announced", "विजेता!"),
+    (
+        "player_found_in",
+        "{name} ने {attempts} अनुमानों में {guess} ढूँढा।",
+    ),
+    ("match_rating", "{player} रेटिंग {rating} ({change})"),
+    ("no_rated_games_for", "{player} के अभी कोई रेटेड खेल नहीं हैं।"),
+    ("no_rated_games", "अभी कोई रेटेड खेल नहीं है।"),
+    ("column_rating", "रेटिंग"),
+    ("column_peak", "शिखर"),
+    ("column_games", "खेल"),
+    ("error", "त्रुटि: {message}"),
+    ("warning", "चेतावनी: {message}"),
+    (
+        "theme_failed",
+        "थीम {name} लोड नहीं हो सकी: {error} (अंतर्निहित थीम: {themes})",
+    ),
+    ("replay_failed", "{path} दोबारा नहीं चलाया जा सका: {error}"),
+    ("batch_unreadable", "{failures} खेल पढ़े नहीं जा सके"),
+    ("batch_failed", "बैच नहीं चलाया जा सका: {error}"),
+    ("resume_failed", "{path} से खेल जारी नहीं किया जा सका: {error}"),
+    ("no_data_dir", "डेटा फ़ोल्डर नहीं मिला, परिणाम सहेजा नहीं गया"),
+    (
+        "no_data_dir_daily",
+        "डेटा फ़ोल्डर नहीं मिला, परिणाम सहेजा नहीं जाएगा",
+    ),
+    (
+        "result_not_saved",
+        "परिणाम {path} में सहेजा नहीं जा सका: {error}",
+    ),
+    ("daily_check_failed", "आज के परिणाम जाँचे नहीं जा सके: {error}"),
+    (
+        "achievements_not_saved",
+        "उपलब्धियाँ {path} में सहेजी नहीं जा सकीं: {error}",
+    ),
+    ("rating_not_saved", "रेटिंग {path} में सहेजी नहीं जा सकी: {error}"),
+    (
+        "ratings_not_saved",
+        "रेटिंग {path} में सहेजी नहीं जा सकीं: {error}",
+    ),
+    ("ratings_unreadable", "रेटिंग पढ़ी नहीं जा सकीं: {error}"),
+    ("leaderboard_unreadable", "लीडरबोर्ड पढ़ा नहीं जा सका: {error}"),
+    ("gave_up", "{strategy} ने हार मान ली: {error}"),
+    ("strategy_failed", "{strategy}: {error}"),
+    (
+        "inconsistent_feedback",
+        "बीज {seed} वाले खेल ने अनुमान {guess} का उत्तर {outcome} दिया, पर गुप्त संख्या {secret} थी",
+    ),
+    ("bench_inconsistent", "असंगत उत्तर: {error}"),
+    (
+        "not_solved",
+        "बीज {seed} वाला खेल {guesses} अनुमानों में हल नहीं हुआ",
+    ),
+    (
+        "player_count",
+        "हॉट-सीट खेल के लिए {min} से {max} खिलाड़ी चाहिए, {count} नहीं",
+    ),
+    (
+        "unknown_difficulty",
+        "अज्ञात कठिनाई `{name}` (easy, normal, hard या insane होनी चाहिए)",
+    ),
+    (
+        "unknown_color",
+        "अज्ञात रंग सेटिंग `{value}` (auto, always या never होनी चाहिए)",
+    ),
+    (
+        "unknown_language",
+        "अज्ञात भाषा `{name}` ({expected} में से एक होनी चाहिए)",
+    ),
+    ("save_corrupt", "सहेजी गई फ़ाइल खराब है: {reason}"),
+    (
+        "save_version",
+        "सहेजी गई फ़ाइल खराब है: संस्करण {version} समर्थित नहीं है",
+    ),
+    ("save_tampered", "सहेजी गई फ़ाइल सहेजने के बाद बदली गई है"),
+    ("save_invalid", "सहेजी गई फ़ाइल मान्य खेल नहीं है: {reason}"),
+    ("secret_unreadable", "गुप्त संख्या पढ़ी नहीं जा सकती"),
+    ("secret_outside_range", "गुप्त संख्या सीमा से बाहर है"),
+    ("impossible_guess", "एक अनुमान लगाया ही नहीं जा सकता था"),
+    ("achievement_first_try", "मनपाठक"),
+    ("achievement_first_try_about", "पहले ही अनुमान में जीतिए।"),
+    ("achievement_perfect_bisection", "द्विभाजन उस्ताद"),
+    (
+        "achievement_perfect_bisection_about",
+        "हमेशा बची हुई संभव संख्याओं के बीच की संख्या चुनकर जीतिए।",
+    ),
+    ("achievement_photo_finish", "आख़िरी पल"),
+    ("achievement_photo_finish_about", "आख़िरी बचे प्रयास में जीतिए।"),
+    ("achievement_hot_streak", "धुआँधार"),
+    (
+        "achievement_hot_streak_about",
+        "एक ही कठिनाई पर लगातार 10 खेल जीतिए।",
+    ),
+    ("achievement_centurion", "शतकवीर"),
+    (
+        "achievement_centurion_about",
+        "एक ही कठिनाई पर 100 खेल खेलिए।",
+    ),
+    ("no_games_recorded", "अभी तक कोई खेल दर्ज नहीं हुआ।"),
+    ("column_difficulty", "कठिनाई"),
+    ("column_played", "खेले"),
+    ("column_wins", "जीत"),
+    ("column_best", "सर्वश्रेष्ठ"),
+    ("column_average", "औसत"),
+    ("column_achievements", "उपलब्धियाँ"),
+    ("column_speedrunner", "स्पीडरनर"),
+    ("column_score", "अंक"),
+    ("column_time", "समय"),
+    ("column_tries", "प्रयास"),
+    ("column_strategy", "रणनीति"),
+    ("column_mean", "औसत"),
+    ("column_median", "माध्यिका"),
+    ("column_worst", "सबसे ख़राब"),
+    ("missing_value", "{flag} को एक मान चाहिए"),
+    ("invalid_number", "{flag} को पूर्ण संख्या चाहिए, मिला `{value}`"),
+    (
+        "empty_range",
+        "सीमा {min}..={max} खाली है, --min को --max से छोटा होना चाहिए",
+    ),
+    ("no_attempts", "--attempts कम से कम 1 होना चाहिए"),
+    ("no_time", "--time-limit कम से कम 1 सेकंड होना चाहिए"),
+    ("missing_file", "replay को चलाने के लिए सत्र का लॉग चाहिए"),
+    (
+        "invalid_speed",
+        "--speed को शून्य या धनात्मक संख्या चाहिए, मिला `{value}`",
+    ),
+    (
+        "unknown_strategy",
+        "अज्ञात रणनीति `{name}` ({expected} में से एक होनी चाहिए)",
+    ),
+    (
+        "too_many_lies",
+        "--lies अधिकतम {max} झूठ की अनुमति देता है, मिला {lies}",
+    ),
+    ("conflict", "{first} को {second} के साथ नहीं मिलाया जा सकता"),
+    ("impossible_code", "{rules} वाला कोई कोड नहीं हो सकता"),
+    ("unexpected_argument", "अनपेक्षित तर्क `{arg}`"),
+    (
+        "usage",
+        "\
+उपयोग: guessing-game [OPTIONS]
+       guessing-game --stats
+       guessing-game --ratings [--name <NAME>]
+       guessing-game --serve <ADDR> | --connect <ADDR>
+       guessing-game --bench <GAMES> [--strategy <NAME>]... [--csv]
+       guessing-game replay <FILE> [--speed <FACTOR>]
+       guessing-game batch [FILE]
+
+हर रूप --color और --theme भी लेता है।
+
+विकल्प:
+  -d, --difficulty <NAME>  easy (1-10), normal (1-100), hard (1-10000)
+                           या insane (1-4294967295) [डिफ़ॉल्ट: normal]
+      --min <N>            सबसे छोटी संभव गुप्त संख्या, स्तर की सीमा बदलती है
+      --max <N>            सबसे बड़ी संभव गुप्त संख्या, स्तर की सीमा बदलती है
+      --attempts <N>       प्रयासों की संख्या [डिफ़ॉल्ट: स्तर के प्रयास]
+      --hints              स्तर के संकेत दें: हर अनुमान के बाद गर्म/ठंडा,
+                           `hint parity` और `hint divisor`
+      --insight            हर अनुमान के बाद बची संभव संख्याएँ और उससे मिली
+                           जानकारी के बिट दिखाएँ
+      --daily              आज की पहेली खेलें, एक ही UTC दिन पर सबके लिए एक
+                           जैसी; दिन में एक बार
+      --speedrun           घड़ी से होड़: हर अनुमान का समय गिना जाता है और अंक
+                           तेज़ी को पुरस्कृत करते हैं; निजी रिकॉर्ड रखे जाते हैं
+      --time-limit <SECS>  SECS सेकंड बीतने पर स्पीडरन हार माना जाए
+      --seed <N>           गुप्त संख्या तय करें ताकि सत्र दोहराया जा सके
+      --reveal             गुप्त संख्या दिखाएँ (उपनाम: --debug)
+      --players <N>        एक टर्मिनल पर 2 से 8 खिलाड़ियों का हॉट-सीट खेल
+      --reverse            संख्या आप चुनें और कंप्यूटर उसका अनुमान लगाए
+      --evil               मेज़बान कोई संख्या नहीं चुनता और जब तक ईमानदारी से
+                           हो सके हर अनुमान से बचता है
+      --code               अंकों का कोड तोड़ें, बुल और काउ में अंक मिलते हैं
+      --length <N>         कोड के अंक, अधिकतम 6 [डिफ़ॉल्ट: स्तर के अनुसार]
+      --repeats            कोड में किसी अंक को एक से अधिक बार आने दें
+      --no-repeats         कोड में दोहराए अंक मना करें
+      --word               इसके बजाय पाँच अक्षरों का छिपा शब्द बूझें
+      --hard-mode          शब्द खेल में हर अनुमान में अब तक मिले अक्षर रखने
+                           होंगे
+      --lies <K>           मेज़बान अपने higher/lower उत्तरों में से K (अधिकतम 5)
+                           तक में झूठ बोल सकता है
+      --resume <FILE>      `:save [FILE]` लिखकर सहेजा गया खेल जारी रखें
+      --record <FILE>      सत्र दर्ज करें ताकि उसे बाद में दोबारा चलाया जा सके
+      --speed <FACTOR>     रीप्ले की गति, बिना रुके चलाने के लिए 0 [डिफ़ॉल्ट: 1]
+      --lang <LANG>        en, hi या es [डिफ़ॉल्ट: LC_ALL, LC_MESSAGES या LANG
+                           से]
+      --name <NAME>        लीडरबोर्ड के लिए खिलाड़ी का नाम, सवाल छोड़ देता है
+      --stats              लीडरबोर्ड दिखाकर बाहर निकलें
+      --ratings            सबकी रेटिंग उसके इतिहास के चार्ट के साथ दिखाएँ, या
+                           केवल --name वाले खिलाड़ी की
+      --serve <ADDR>       ADDR पर मल्टीप्लेयर खेल चलाएँ, जैसे 0.0.0.0:7878
+      --connect <ADDR>     ADDR पर चल रहे मल्टीप्लेयर खेल से जुड़ें
+      --bench <GAMES>      हर रणनीति से GAMES बीज वाले खेल खेलें और औसत,
+                           माध्यिका और सबसे ख़राब प्रयास बताएँ
+      --strategy <NAME>    linear, random, bisection या biased; कई की तुलना
+                           के लिए दोहराएँ [डिफ़ॉल्ट: सभी]
+      --csv                बेंचमार्क के परिणाम CSV में दिखाएँ
+      --color <WHEN>       auto, always या never [डिफ़ॉल्ट: auto, जो NO_COLOR
+                           न होने पर टर्मिनल को रंगता है, और CLICOLOR_FORCE
+                           होने पर पाइप को भी]
+      --theme <THEME>      default, colorblind, या कोई JSON थीम फ़ाइल
+                           [डिफ़ॉल्ट: डेटा फ़ोल्डर में theme.json, अगर हो]
+  -h, --help               यह सहायता दिखाएँ
+
+batch मोड FILE से, या FILE न होने या `-` होने पर stdin से, JSON Lines में
+लिखे खेल पढ़ता है और हर खेल की एक JSON रिपोर्ट छापता है।",
+    ),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("guess_the_number", "¡Adivina el número!"),
+    ("whats_your_name", "¿Cómo te llamas?"),
+    ("secret_is", "El número secreto es: {secret}"),
+    (
+        "please_guess",
+        "Escribe un número entre {min} y {max}. Tienes {attempts} intentos.",
+    ),
+    ("hints_on_offer", "Pistas disponibles: {hints}."),
+    ("hint_cost", "`hint {kind}` (-{cost} puntos)"),
+    ("you_guessed", "Has dicho: {guess}"),
+    ("too_big", "¡Demasiado grande!"),
+    ("too_small", "¡Demasiado pequeño!"),
+    ("one_try_left", "Te queda 1 intento."),
+    ("tries_left", "Te quedan {left} intentos."),
+    ("you_win", "¡Has ganado!"),
+    ("score", "Puntos: {score}"),
+    ("you_lose", "Has perdido, el número era {secret}."),
+    ("cannot_save", "Esta partida no se puede guardar."),
+    (
+        "game_saved",
+        "Partida guardada en {path}. Continúala con --resume {path}",
+    ),
+    ("could_not_save", "No se pudo guardar en {path}: {error}"),
+    (
+        "welcome_back",
+        "¡Hola de nuevo, {player}! Llevas {time} de partida.",
+    ),
+    ("was_too_big", "Dijiste {guess}: demasiado grande."),
+    ("was_too_small", "Dijiste {guess}: demasiado pequeño."),
+    ("rating", "Puntuación: {rating} ({change})."),
+    (
+        "unlocked",
+        "🏆 {player} ha desbloqueado {name}: {description}",
+    ),
+    ("duration", "{minutes} min {seconds} s"),
+    ("seconds", "{seconds} s"),
+    ("empty_guess", "Escribe un número."),
+    ("not_a_number", "`{input}` no es un número."),
+    (
+        "negative",
+        "`{input}` es negativo, los números deben ser positivos.",
+    ),
+    (
+        "too_large",
+        "`{input}` es demasiado grande, el mayor número posible es {max}.",
+    ),
+    (
+        "input_closed",
+        "la entrada se cerró antes de terminar la partida",
+    ),
+    ("read_failed", "no se pudo leer la entrada: {error}"),
+    (
+        "out_of_range",
+        "{guess} está fuera de rango, el secreto está entre {min} y {max}",
+    ),
+    ("game_over", "la partida ya ha terminado"),
+    ("warmer", "Más caliente."),
+    ("colder", "Más frío."),
+    ("same_distance", "Ni más caliente ni más frío."),
+    ("even", "El número es par."),
+    ("odd", "El número es impar."),
+    ("divisible_by", "El número es divisible entre {divisor}."),
+    (
+        "not_divisible_by",
+        "El número no es divisible entre {divisor}.",
+    ),
+    (
+        "unknown_hint",
+        "No hay ninguna pista `{kind}`, prueba warmer, parity o divisor.",
+    ),
+    (
+        "hint_disabled",
+        "La pista {kind} no está disponible en esta dificultad.",
+    ),
+    (
+        "hint_unavailable",
+        "Todavía no hay ninguna pista {kind} que dar.",
+    ),
+    ("only_possible", "Solo queda posible el {number}."),
+    (
+        "still_possible",
+        "Todavía posibles: del {low} al {high} ({count} números).",
+    ),
+    (
+        "bits_gained",
+        "Este intento aportó {bits} bits, una bisección asegura {ideal}.",
+    ),
+    ("efficiency_so_far", "Eficiencia hasta ahora: {percent} %."),
+    ("efficiency_overall", "Eficiencia total: {percent} %."),
+    ("clock_running", "¡El reloj está en marcha!"),
+    (
+        "clock_running_limit",
+        "¡El reloj está en marcha! Gana en menos de {limit}.",
+    ),
+    ("times_up", "¡Se acabó el tiempo!"),
+    ("number_was", "El número era {secret}."),
+    (
+        "speedrun_time",
+        "Tiempo: {time} (par {par}). Puntos de speedrun: {score}",
+    ),
+    (
+        "guess_times",
+        "Tiempos por intento: {times} (media {average})",
+    ),
+    ("new_personal_best", "¡Nuevo récord personal!"),
+    (
+        "personal_best",
+        "Récord personal en {difficulty}: {score} en {time}.",
+    ),
+    (
+        "already_played",
+        "Ya has jugado el reto #{number} hoy. ¡Vuelve mañana!",
+    ),
+    ("daily_challenge", "Reto diario #{number} del {date}."),
+    ("share_result", "Comparte tu resultado:"),
+    ("player_name", "Jugador {number}, ¿cómo te llamas?"),
+    ("name_taken", "{name} ya está jugando, elige otro nombre."),
+    (
+        "everyone_guess",
+        "Adivina un número entre {min} y {max}. Cada uno tiene {attempts} intentos.",
+    ),
+    ("your_guess", "{player}, tu número:"),
+    ("winner", "Ganador:"),
+    (
+        "player_found",
+        "{winner} ¡{player} ha encontrado el {secret}!",
+    ),
+    ("nobody_found", "Nadie lo encontró, el número era {secret}."),
+    ("column_player", "Jugador"),
+    ("column_attempts", "Intentos"),
+    ("column_closest", "Más cerca"),
+    ("column_result", "Resultado"),
+    ("seat_won", "ganó"),
+    ("seat_out", "sin intentos"),
+    ("seat_playing", "sigue adivinando"),
+    (
+        "replaying",
+        "Reproduciendo {path} (semilla {seed}, del {min} al {max}).",
+    ),
+    (
+        "replay_matches",
+        "La reproducción coincide con la grabación.",
+    ),
+    (
+        "replay_saved",
+        "La grabación termina donde el jugador escribió :save, antes de acabar la partida.",
+    ),
+    (
+        "replay_diverged",
+        "la línea {line} ya no coincide con la grabación: se esperaba {expected}, se obtuvo {got}",
+    ),
+    (
+        "think_of_number",
+        "Piensa un número entre {min} y {max} y lo adivinaré.",
+    ),
+    (
+        "answer_with",
+        "Responde a cada intento con higher (mayor), lower (menor) o correct (correcto).",
+    ),
+    ("is_it", "¿Es el {guess}?"),
+    ("please_answer", "Responde higher, lower o correct."),
+    ("cheating", "Estás haciendo trampa: {contradiction}."),
+    ("got_it", "¡Lo tengo!"),
+    (
+        "your_number_is",
+        "Tu número es el {number}, encontrado en {guesses} intentos.",
+    ),
+    ("answer_higher", "mayor que {guess}"),
+    ("answer_lower", "menor que {guess}"),
+    ("answer_exactly", "exactamente {guess}"),
+    (
+        "contradicts",
+        "la respuesta #{number} ({answer}) contradice la respuesta #{earlier} ({earlier_answer})",
+    ),
+    (
+        "outside_range",
+        "la respuesta #{number} ({answer}) está fuera del rango que elegiste",
+    ),
+    (
+        "not_picked",
+        "Todavía no he elegido un número, así que piensa bien.",
+    ),
+    ("cornered", "Me has acorralado en {guesses} intentos."),
+    ("one_number", "1 número"),
+    ("numbers", "{count} números"),
+    (
+        "one_try_left_possible",
+        "Te queda 1 intento y {possible} en juego.",
+    ),
+    (
+        "tries_left_possible",
+        "Te quedan {left} intentos y {possible} en juego.",
+    ),
+    ("still_in_play", "{possible} en juego."),
+    (
+        "may_lie_once",
+        "Cuidado: puedo mentir en 1 de mis respuestas.",
+    ),
+    (
+        "may_lie",
+        "Cuidado: puedo mentir en hasta {lies} de mis respuestas.",
+    ),
+    ("my_answers", "Mis respuestas fueron:"),
+    ("a_lie", "(mentira)"),
+    ("secret_code", "El código secreto es: {code}"),
+    (
+        "crack_the_code",
+        "Descifra el código: {rules}. Tienes {attempts} intentos.",
+    ),
+    (
+        "bulls_and_cows",
+        "Un toro es un dígito correcto en su sitio, una vaca un dígito correcto fuera de sitio. Escribe `hint` para una sugerencia.",
+    ),
+    (
+        "code_rules_repeats",
+        "{length} dígitos del 0 al {highest}, se pueden repetir",
+    ),
+    (
+        "code_rules_unique",
+        "{length} dígitos del 0 al {highest}, sin repetir",
+    ),
+    ("empty_code", "Escribe un código."),
+    (
+        "code_length",
+        "El código tiene {expected} dígitos, no {got}.",
+    ),
+    ("not_a_digit", "`{digit}` no es uno de los dígitos."),
+    (
+        "repeated_digit",
+        "{digit} aparece más de una vez y no se puede repetir.",
+    ),
+    ("bulls_cows", "{bulls}, {cows}"),
+    ("one_bull", "1 toro"),
+    ("bulls", "{count} toros"),
+    ("one_cow", "1 vaca"),
+    ("cows", "{count} vacas"),
+    (
+        "one_code_possible",
+        "Queda 1 código posible. Prueba {code}.",
+    ),
+    (
+        "codes_possible",
+        "Quedan {count} códigos posibles. Prueba {code}.",
+    ),
+    ("cracked_in", "Descifrado en {guesses} intentos."),
+    ("code_was", "Has perdido, el código era {code}."),
+    ("feedback_and_tries", "{feedback}. {tries}"),
+    (
+        "solver_cracks",
+        "El solucionador descifra este código en {guesses} intentos.",
+    ),
+    ("secret_word", "La palabra secreta es: {word}"),
+    (
+        "guess_the_word",
+        "Adivina la palabra de {length} letras. Tienes {attempts} intentos.",
+    ),
+    (
+        "hard_mode",
+        "Modo difícil: cada intento debe conservar las letras descubiertas.",
+    ),
+    ("found_in", "Encontrada en {guesses} intentos."),
+    ("word_was", "Has perdido, la palabra era {word}."),
+    ("empty_word", "Escribe una palabra."),
+    (
+        "word_length",
+        "Las palabras tienen {length} letras, no {got}.",
+    ),
+    ("not_a_letter", "`{letter}` no es una letra."),
+    ("not_a_word", "`{word}` no está en la lista de palabras."),
+    (
+        "letter_moved",
+        "Modo difícil: la letra {position} debe ser {letter}.",
+    ),
+    (
+        "letter_missing",
+        "Modo difícil: el intento debe contener la {letter}.",
+    ),
+    (
+        "hosting",
+        "Partida abierta en {addr}, el secreto está entre {min} y {max}.",
+    ),
+    (
+        "joined_as",
+        "Te has unido como {name}. Adivina un número entre {min} y {max}.",
+    ),
+    ("secret_between", "El secreto está entre {min} y {max}."),
+    ("player_joined", "{name} se ha unido a la partida."),
+    ("player_guessed", "{name} dijo {guess}: {hint}"),
+    ("winner_announced", "¡Ganador!"),
+    (
+        "player_found_in",
+        "{name} encontró el {guess} en {attempts} intentos.",
+    ),
+    ("match_rating", "{player} puntuación {rating} ({change})"),
+    (
+        "no_rated_games_for",
+        "{player} aún no tiene partidas puntuadas.",
+    ),
+    ("no_rated_games", "Aún no hay partidas puntuadas."),
+    ("column_rating", "Puntuación"),
+    ("column_peak", "Máximo"),
+    ("column_games", "Partidas"),
+    ("error", "error: {message}"),
+    ("warning", "aviso: {message}"),
+    (
+        "theme_failed",
+        "no se pudo cargar el tema {name}: {error} (temas incluidos: {themes})",
+    ),
+    ("replay_failed", "no se pudo reproducir {path}: {error}"),
+    (
+        "batch_unreadable",
+        "no se pudieron leer {failures} de las partidas",
+    ),
+    ("batch_failed", "no se pudo ejecutar el lote: {error}"),
+    ("resume_failed", "no se pudo continuar {path}: {error}"),
+    (
+        "no_data_dir",
+        "no se encontró la carpeta de datos, el resultado no se guardó",
+    ),
+    (
+        "no_data_dir_daily",
+        "no se encontró la carpeta de datos, el resultado no se guardará",
+    ),
+    (
+        "result_not_saved",
+        "no se pudo guardar el resultado en {path}: {error}",
+    ),
+    (
+        "daily_check_failed",
+        "no se pudieron comprobar los resultados de hoy: {error}",
+    ),
+    (
+        "achievements_not_saved",
+        "no se pudieron guardar los logros en {path}: {error}",
+    ),
+    (
+        "rating_not_saved",
+        "no se pudo guardar la puntuación en {path}: {error}",
+    ),
+    (
+        "ratings_not_saved",
+        "no se pudieron guardar las puntuaciones en {path}: {error}",
+    ),
+    (
+        "ratings_unreadable",
+        "no se pudieron leer las puntuaciones: {error}",
+    ),
+    (
+        "leaderboard_unreadable",
+        "no se pudo leer la clasificación: {error}",
+    ),
+    ("gave_up", "{strategy} se rindió: {error}"),
+    ("strategy_failed", "{strategy}: {error}"),
+    (
+        "inconsistent_feedback",
+        "la partida con semilla {seed} respondió {outcome} a {guess}, pero el número secreto era {secret}",
+    ),
+    ("bench_inconsistent", "respuesta incoherente: {error}"),
+    (
+        "not_solved",
+        "la partida con semilla {seed} no se resolvió en {guesses} intentos",
+    ),
+    (
+        "player_count",
+        "las partidas por turnos necesitan de {min} a {max} jugadores, no {count}",
+    ),
+    (
+        "unknown_difficulty",
+        "dificultad desconocida `{name}` (se esperaba easy, normal, hard o insane)",
+    ),
+    (
+        "unknown_color",
+        "ajuste de color desconocido `{value}` (se esperaba auto, always o never)",
+    ),
+    (
+        "unknown_language",
+        "idioma desconocido `{name}` (se esperaba {expected})",
+    ),
+    ("save_corrupt", "el archivo guardado está dañado: {reason}"),
+    (
+        "save_version",
+        "el archivo guardado está dañado: versión {version} no admitida",
+    ),
+    (
+        "save_tampered",
+        "el archivo guardado se ha modificado después de guardarlo",
+    ),
+    (
+        "save_invalid",
+        "el archivo guardado no es una partida válida: {reason}",
+    ),
+    ("secret_unreadable", "el número secreto no se puede leer"),
+    (
+        "secret_outside_range",
+        "el número secreto está fuera del rango",
+    ),
+    ("impossible_guess", "uno de los intentos no se pudo hacer"),
+    ("achievement_first_try", "Lector de mentes"),
+    ("achievement_first_try_about", "Gana al primer intento."),
+    ("achievement_perfect_bisection", "Buscador binario"),
+    (
+        "achievement_perfect_bisection_about",
+        "Gana eligiendo siempre el centro de los números aún posibles.",
+    ),
+    ("achievement_photo_finish", "Al límite"),
+    (
+        "achievement_photo_finish_about",
+        "Gana en el último intento permitido.",
+    ),
+    ("achievement_hot_streak", "En racha"),
+    (
+        "achievement_hot_streak_about",
+        "Gana 10 partidas seguidas en una dificultad.",
+    ),
+    ("achievement_centurion", "Centurión"),
+    (
+        "achievement_centurion_about",
+        "Juega 100 partidas en una dificultad.",
+    ),
+    ("no_games_recorded", "Todavía no hay partidas registradas."),
+    ("column_difficulty", "Dificultad"),
+    ("column_played", "Jugadas"),
+    ("column_wins", "Ganadas"),
+    ("column_best", "Mejor"),
+    ("column_average", "Media"),
+    ("column_achievements", "Logros"),
+    ("column_speedrunner", "Speedrunner"),
+    ("column_score", "Puntos"),
+    ("column_time", "Tiempo"),
+    ("column_tries", "Intentos"),
+    ("column_strategy", "Estrategia"),
+    ("column_mean", "Media"),
+    ("column_median", "Mediana"),
+    ("column_worst", "Peor"),
+    ("missing_value", "{flag} necesita un valor"),
+    (
+        "invalid_number",
+        "{flag} espera un número entero, no `{value}`",
+    ),
+    (
+        "empty_range",
+        "el rango {min}..={max} está vacío, --min debe ser menor que --max",
+    ),
+    ("no_attempts", "--attempts debe ser al menos 1"),
+    ("no_time", "--time-limit debe ser de al menos 1 segundo"),
+    (
+        "missing_file",
+        "replay necesita un registro de sesión que reproducir",
+    ),
+    (
+        "invalid_speed",
+        "--speed espera un número no negativo, no `{value}`",
+    ),
+    (
+        "unknown_strategy",
+        "estrategia desconocida `{name}` (se esperaba {expected})",
+    ),
+    (
+        "too_many_lies",
+        "--lies permite como mucho {max} mentiras, no {lies}",
+    ),
+    ("conflict", "{first} no se puede combinar con {second}"),
+    ("impossible_code", "no existe ningún código de {rules}"),
+    ("unexpected_argument", "argumento inesperado `{arg}`"),
+    (
+        "usage",
+        "\
+Uso: guessing-game [OPTIONS]
+     guessing-game --stats
+     guessing-game --ratings [--name <NAME>]
+     guessing-game --serve <ADDR> | --connect <ADDR>
+     guessing-game --bench <GAMES> [--strategy <NAME>]... [--csv]
+     guessing-game replay <FILE> [--speed <FACTOR>]
+     guessing-game batch [FILE]
+
+Todas las formas aceptan también --color y --theme.
+
+Opciones:
+  -d, --difficulty <NAME>  easy (1-10), normal (1-100), hard (1-10000)
+                           o insane (1-4294967295) [por defecto: normal]
+      --min <N>            Número secreto más bajo, sustituye al del nivel
+      --max <N>            Número secreto más alto, sustituye al del nivel
+      --attempts <N>       Intentos disponibles [por defecto: los del nivel]
+      --hints              Ofrece las pistas del nivel: más caliente/más frío
+                           tras cada intento, `hint parity` y `hint divisor`
+      --insight            Tras cada intento, muestra los números aún posibles
+                           y los bits de información que aportó
+      --daily              Juega el reto de hoy, el mismo para todos el mismo
+                           día UTC; una vez al día
+      --speedrun           Contra el reloj: cada intento se cronometra y la
+                           puntuación premia la rapidez; se guardan los récords
+      --time-limit <SECS>  Da el speedrun por perdido al pasar SECS segundos
+      --seed <N>           Fija el número secreto para repetir una sesión
+      --reveal             Muestra el número secreto (alias: --debug)
+      --players <N>        Partida por turnos de 2 a 8 jugadores en un terminal
+      --reverse            Tú eliges el número y el ordenador lo adivina
+      --evil               El anfitrión no elige número y esquiva cada intento
+                           mientras pueda hacerlo honradamente
+      --code               Descifra un código de dígitos, con toros y vacas
+      --length <N>         Dígitos del código, hasta 6 [por defecto: los del
+                           nivel]
+      --repeats            Permite que un dígito aparezca varias veces
+      --no-repeats         Prohíbe los dígitos repetidos en el código
+      --word               Adivina una palabra oculta de cinco letras
+      --hard-mode          En el modo palabra, cada intento debe conservar las
+                           letras ya descubiertas
+      --lies <K>           El anfitrión puede mentir en hasta K (como mucho 5)
+                           de sus respuestas higher/lower
+      --resume <FILE>      Continúa una partida guardada con `:save [FILE]`
+      --record <FILE>      Registra la sesión para reproducirla después
+      --speed <FACTOR>     Aceleración de la repetición, 0 sin pausas
+                           [por defecto: 1]
+      --lang <LANG>        en, hi o es [por defecto: según LC_ALL, LC_MESSAGES
+                           o LANG]
+      --name <NAME>        Nombre para la clasificación, omite la pregunta
+      --stats              Muestra la clasificación y sale
+      --ratings            Muestra la puntuación de cada jugador con un gráfico
+                           de su historial, o solo la del jugador de --name
+      --serve <ADDR>       Aloja una partida multijugador en ADDR, p. ej.
+                           0.0.0.0:7878
+      --connect <ADDR>     Se une a una partida multijugador alojada en ADDR
+      --bench <GAMES>      Juega GAMES partidas con semilla por estrategia e
+                           informa de la media, la mediana y el peor resultado
+      --strategy <NAME>    linear, random, bisection o biased; repítela para
+                           comparar varias [por defecto: todas]
+      --csv                Muestra los resultados de --bench en CSV
+      --color <WHEN>       auto, always o never [por defecto: auto, que colorea
+                           un terminal salvo si NO_COLOR está definida, y
+                           también las tuberías si lo está CLICOLOR_FORCE]
+      --theme <THEME>      default, colorblind o un archivo de tema JSON
+                           [por defecto: theme.json en la carpeta de datos,
+                           si existe]
+  -h, --help               Muestra esta ayuda
+
+El modo batch lee partidas guionizadas en JSON Lines desde FILE, o desde la
+entrada estándar si falta FILE o es `-`, y escribe un informe JSON por partida.",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn keys(lang: Lang) -> BTreeSet<&'static str> {
+        lang.catalog().iter().map(|(key, _)| *key).collect()
+    }
+
+    fn placeholders(template: &str) -> BTreeSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}'))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    #[test]
+    fn every_catalog_has_every_key() {
+        let english = keys(Lang::En);
+        assert_eq!(english.len(), EN.len(), "English repeats a key");
+        for lang in Lang::ALL {
+            assert_eq!(keys(lang), english, "{} is missing or adding keys", lang);
+            for key in &english {
+                assert_eq!(
+                    placeholders(lang.template(key)),
+                    placeholders(Lang::En.template(key)),
+                    "{} has different placeholders for {}",
+                    lang,
+                    key
+                );
+            }
+        }
+    }
+
+    /// Every key the code looks up with `tr` or `Lang::format`. Keys are
+    /// always written out at the call, so the calls can be found in the
+    /// source.
+    fn used_keys() -> BTreeSet<&'static str> {
+        const SOURCES: [&str; 16] = [
+            include_str!("main.rs"),
+            include_str!("cli.rs"),
+            include_str!("i18n.rs"),
+            include_str!("input.rs"),
+            include_str!("game.rs"),
+            include_str!("hints.rs"),
+            include_str!("mastermind.rs"),
+            include_str!("wordle.rs"),
+            include_str!("reverse.rs"),
+            include_str!("stats.rs"),
+            include_str!("achievements.rs"),
+            include_str!("save.rs"),
+            include_str!("bench.rs"),
+            include_str!("hotseat.rs"),
+            include_str!("difficulty.rs"),
+            include_str!("theme.rs"),
+        ];
+        let mut keys = BTreeSet::new();
+        for source in SOURCES {
+            for call in ["tr(", ".format("] {
+                for (at, _) in source.match_indices(call) {
+                    // Skip the ends of longer names, like `from_str(`.
+                    let inside_name = call == "tr("
+                        && source[..at]
+                            .chars()
+                            .next_back()
+                            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+                    let argument = source[at + call.len()..].trim_start();
+                    if let (false, Some(rest)) = (inside_name, argument.strip_prefix('"')) {
+                        // Keys are snake_case, which also skips the string
+                        // literals in this scanner.
+                        keys.extend(
+                            rest.split_once('"').map(|(key, _)| key).filter(|key| {
+                                key.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+                            }),
+                        );
+                    }
+                }
+            }
+        }
+        keys
+    }
+
+    #[test]
+    fn every_key_used_is_in_the_catalogs() {
+        let used = used_keys();
+        for key in [
+            "tries_left",
+            "too_large",
+            "usage",
+            "conflict",
+            "column_wins",
+        ] {
+            assert!(used.contains(key), "{} was not found", key);
+        }
+        for lang in Lang::ALL {
+            let known = keys(lang);
+            let missing: Vec<_> = used.difference(&known).collect();
+            assert!(missing.is_empty(), "{} is missing {:?}", lang, missing);
+        }
+    }
+
+    #[test]
+    fn templates_are_filled_in() {
+        assert_eq!(
+            Lang::Es.format("tries_left", &[("left", &3)]),
+            "Te quedan 3 intentos."
+        );
+        assert_eq!(
+            Lang::En.format("game_saved", &[("path", &"a.save")]),
+            "Game saved to a.save. Continue it with --resume a.save"
+        );
+        assert_eq!(Lang::Hi.template("no such key"), "no such key");
+    }
+
+    #[test]
+    fn numbers_follow_the_locale() {
+        assert_eq!(Lang::En.number(999u32), "999");
+        assert_eq!(Lang::En.number(10_000u32), "10,000");
+        assert_eq!(Lang::En.number(u32::MAX), "4,294,967,295");
+        assert_eq!(Lang::Es.number(1_234u32), "1234");
+        assert_eq!(Lang::Es.number(12_345u32), "12.345");
+        assert_eq!(Lang::Hi.number(1_000u32), "1,000");
+        assert_eq!(Lang::Hi.number(12_345_678u32), "1,23,45,678");
+        assert_eq!(Lang::Hi.number(u32::MAX), "4,29,49,67,295");
+    }
+
+    #[test]
+    fn locales_name_a_language() {
+        assert_eq!("es_MX.UTF-8".parse(), Ok(Lang::Es));
+        assert_eq!("hi_IN".parse(), Ok(Lang::Hi));
+        assert_eq!("C.UTF-8".parse(), Ok(Lang::En));
+        assert_eq!("EN".parse(), Ok(Lang::En));
+        assert!("fr_FR".parse::<Lang>().is_err());
+    }
+}
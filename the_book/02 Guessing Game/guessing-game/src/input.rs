@@ -0,0 +1,156 @@
+use crate::i18n::{Lang, Localize};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, BufRead};
+use std::num::IntErrorKind;
+
+/// Why a line of input could not be turned into a guess.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParseGuessError {
+    Empty,
+    NotANumber(String),
+    Negative(String),
+    TooLarge(String),
+}
+
+impl Localize for ParseGuessError {
+    fn localize(&self, lang: Lang) -> String {
+        match self {
+            ParseGuessError::Empty => lang.format("empty_guess", &[]),
+            ParseGuessError::NotANumber(input) => lang.format("not_a_number", &[("input", input)]),
+            ParseGuessError::Negative(input) => lang.format("negative", &[("input", input)]),
+            ParseGuessError::TooLarge(input) => lang.format(
+                "too_large",
+                &[("input", input), ("max", &lang.number(u32::MAX))],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ParseGuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for ParseGuessError {}
+
+/// Parses one line typed by the player, ignoring surrounding whitespace.
+pub fn parse_guess(line: &str) -> Result<u32, ParseGuessError> {
+    let input = line.trim();
+    input
+        .parse()
+        .map_err(|err: std::num::ParseIntError| match err.kind() {
+            IntErrorKind::Empty => ParseGuessError::Empty,
+            IntErrorKind::PosOverflow => ParseGuessError::TooLarge(input.to_string()),
+            _ if is_negative_integer(input) => ParseGuessError::Negative(input.to_string()),
+            _ => ParseGuessError::NotANumber(input.to_string()),
+        })
+}
+
+fn is_negative_integer(input: &str) -> bool {
+    match input.strip_prefix('-') {
+        Some(digits) => !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()),
+        None => false,
+    }
+}
+
+/// Failures while reading from the player's terminal.
+#[derive(Debug)]
+pub enum InputError {
+    /// The input was closed, e.g. stdin reached end of file.
+    Eof,
+    Io(io::Error),
+}
+
+impl Localize for InputError {
+    fn localize(&self, lang: Lang) -> String {
+        match self {
+            InputError::Eof => lang.format("input_closed", &[]),
+            InputError::Io(err) => lang.format("read_failed", &[("error", err)]),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Eof => None,
+            InputError::Io(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for InputError {
+    fn from(err: io::Error) -> InputError {
+        InputError::Io(err)
+    }
+}
+
+/// Reads the next line, reporting a closed input as `InputError::Eof` rather
+/// than as an empty line.
+pub fn read_line<R: BufRead>(input: &mut R) -> Result<String, InputError> {
+    let mut line = String::new();
+    match input.read_line(&mut line)? {
+        0 => Err(InputError::Eof),
+        _ => Ok(line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_numbers_with_whitespace() {
+        assert_eq!(parse_guess("42\n"), Ok(42));
+        assert_eq!(parse_guess("  7  "), Ok(7));
+        assert_eq!(parse_guess("4294967295"), Ok(u32::MAX));
+    }
+
+    #[test]
+    fn classifies_bad_input() {
+        assert_eq!(parse_guess("\n"), Err(ParseGuessError::Empty));
+        assert_eq!(parse_guess("   "), Err(ParseGuessError::Empty));
+        assert_eq!(
+            parse_guess("forty"),
+            Err(ParseGuessError::NotANumber("forty".into()))
+        );
+        assert_eq!(
+            parse_guess("4.5"),
+            Err(ParseGuessError::NotANumber("4.5".into()))
+        );
+        assert_eq!(
+            parse_guess("-3"),
+            Err(ParseGuessError::Negative("-3".into()))
+        );
+        assert_eq!(
+            parse_guess("-"),
+            Err(ParseGuessError::NotANumber("-".into()))
+        );
+        assert_eq!(
+            parse_guess("4294967296"),
+            Err(ParseGuessError::TooLarge("4294967296".into()))
+        );
+    }
+
+    #[test]
+    fn read_line_detects_eof() {
+        let mut input = "12\n".as_bytes();
+        assert_eq!(read_line(&mut input).unwrap(), "12\n");
+        assert!(matches!(read_line(&mut input), Err(InputError::Eof)));
+    }
+
+    #[test]
+    fn blank_line_is_not_eof() {
+        let mut input = "\n".as_bytes();
+        assert_eq!(read_line(&mut input).unwrap(), "\n");
+    }
+}
@@ -0,0 +1,134 @@
+//! Information-theoretic feedback: how far each guess narrowed the secret
+//! down, measured in bits, next to what a perfect bisection would manage.
+
+use crate::game::{Game, GuessOutcome};
+use std::ops::RangeInclusive;
+
+/// What one guess taught the player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub guess: u32,
+    /// How many numbers were possible before the guess.
+    pub before: u64,
+    /// The numbers still possible after it.
+    pub remaining: RangeInclusive<u32>,
+}
+
+impl Step {
+    pub fn after(&self) -> u64 {
+        size(&self.remaining)
+    }
+
+    /// The information the answer gave, in bits.
+    pub fn bits(&self) -> f64 {
+        (self.before as f64 / self.after() as f64).log2()
+    }
+
+    /// The information a bisection is sure to get from the same position.
+    pub fn ideal_bits(&self) -> f64 {
+        ideal_bits(self.before)
+    }
+}
+
+fn size(range: &RangeInclusive<u32>) -> u64 {
+    u64::from(*range.end() - *range.start()) + 1
+}
+
+/// The bits a guess at the midpoint of `size` numbers is sure to gain: in
+/// the worst case it leaves the larger half, which is `size / 2` numbers.
+pub fn ideal_bits(size: u64) -> f64 {
+    if size <= 1 {
+        0.0
+    } else {
+        (size as f64 / (size / 2) as f64).log2()
+    }
+}
+
+/// Replays a game's guesses, tracking the interval the secret must lie in.
+/// A guess outside the interval teaches nothing and gains 0 bits.
+pub fn steps(game: &Game) -> Vec<Step> {
+    let mut low = *game.range().start();
+    let mut high = *game.range().end();
+    game.history()
+        .iter()
+        .map(|&guess| {
+            let before = size(&(low..=high));
+            match GuessOutcome::from(guess.cmp(&game.secret())) {
+                GuessOutcome::TooSmall => low = low.max(guess + 1),
+                GuessOutcome::TooBig => high = high.min(guess - 1),
+                GuessOutcome::Win => (low, high) = (guess, guess),
+            }
+            Step {
+                guess,
+                before,
+                remaining: low..=high,
+            }
+        })
+        .collect()
+}
+
+/// The numbers still possible given every answer so far.
+pub fn remaining(game: &Game) -> RangeInclusive<u32> {
+    steps(game)
+        .pop()
+        .map_or_else(|| game.range().clone(), |step| step.remaining)
+}
+
+/// Bits gained over bits a bisection would have gained across `steps`, so
+/// 1.0 means playing as well as a bisection. Lucky guesses can beat it.
+/// `None` until some guess could have taught something.
+pub fn efficiency(steps: &[Step]) -> Option<f64> {
+    let ideal: f64 = steps.iter().map(Step::ideal_bits).sum();
+    let gained: f64 = steps.iter().map(Step::bits).sum();
+    (ideal > 0.0).then(|| gained / ideal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn played(secret: u32, guesses: &[u32]) -> Game {
+        let mut game = Game::new(secret, 1..=100);
+        for &guess in guesses {
+            game.guess(guess).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn answers_narrow_the_interval() {
+        let game = played(60, &[50, 75, 10]);
+        let steps = steps(&game);
+        assert_eq!(steps[0].remaining, 51..=100);
+        assert_eq!(steps[1].remaining, 51..=74);
+        // Already ruled out, so nothing is learned.
+        assert_eq!(steps[2].remaining, 51..=74);
+        assert_eq!(steps[2].bits(), 0.0);
+        assert_eq!(remaining(&game), 51..=74);
+        assert_eq!(remaining(&played(60, &[])), 1..=100);
+    }
+
+    #[test]
+    fn bisection_is_fully_efficient() {
+        let game = played(100, &[50, 75, 88, 94, 97, 99, 100]);
+        let steps = steps(&game);
+        assert_eq!(steps[0].bits(), 1.0);
+        assert_eq!(steps[0].ideal_bits(), 1.0);
+        assert!(
+            steps
+                .iter()
+                .all(|step| step.bits() >= step.ideal_bits() - 1e-9)
+        );
+        assert!(efficiency(&steps).unwrap() > 1.0 - 1e-9);
+        assert_eq!(remaining(&game), 100..=100);
+    }
+
+    #[test]
+    fn lopsided_guesses_gain_less() {
+        let steps = steps(&played(60, &[10, 20]));
+        assert!(steps[0].bits() < 0.2);
+        assert!(efficiency(&steps).unwrap() < 0.5);
+        assert_eq!(efficiency(&[]), None);
+        assert_eq!(ideal_bits(1), 0.0);
+    }
+}
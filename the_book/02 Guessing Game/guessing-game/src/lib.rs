@@ -0,0 +1,33 @@
+//! The guessing game from chapter 2 of the book, split into a library so the
+//! rules can be exercised without a terminal attached.
+
+pub mod achievements;
+pub mod batch;
+pub mod bench;
+pub mod daily;
+mod difficulty;
+pub mod evil;
+mod game;
+pub mod hints;
+pub mod hotseat;
+pub mod i18n;
+mod input;
+pub mod insight;
+pub mod mastermind;
+pub mod net;
+pub mod rating;
+pub mod record;
+pub mod reverse;
+pub mod save;
+pub mod speedrun;
+mod stats;
+pub mod storage;
+pub mod strategy;
+pub mod theme;
+pub mod ulam;
+pub mod wordle;
+
+pub use difficulty::{Difficulty, ParseDifficultyError};
+pub use game::{Game, GuessError, GuessOutcome, Status, Turn, optimal_attempts, score};
+pub use input::{InputError, ParseGuessError, parse_guess, read_line};
+pub use stats::{Leaderboard, Record};
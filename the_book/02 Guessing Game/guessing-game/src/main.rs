@@ -1,38 +1,1756 @@
+mod cli;
+
+use cli::{Appearance, Command, Options};
 use colored::*;
-use rand::Rng;
-use std::cmp::Ordering;
-use std::io;
+use guessing_game::batch;
+use guessing_game::bench::{self, BenchError};
+use guessing_game::daily::{DailyResult, Puzzle};
+use guessing_game::evil::EvilHost;
+use guessing_game::hints::{self, HintKind};
+use guessing_game::hotseat::HotSeat;
+use guessing_game::i18n::{Lang, Localize};
+use guessing_game::insight;
+use guessing_game::mastermind::{self, CodeGame, CodeTurn};
+use guessing_game::net::{self, ClientMessage, ServerMessage};
+use guessing_game::rating::{self, MatchKind, Ratings};
+use guessing_game::record::{Header, Recorder, Recording};
+use guessing_game::reverse::{self, Guesser};
+use guessing_game::save::SaveFile;
+use guessing_game::speedrun::{self, Speedrun};
+use guessing_game::strategy;
+use guessing_game::theme::{Outcome, Theme};
+use guessing_game::ulam::{self, LyingHost};
+use guessing_game::wordle::{self, Mark, WordGame, WordTurn};
+use guessing_game::{Game, GuessOutcome, InputError, Leaderboard, Status, Turn};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use std::{env, fmt, fs, process, thread};
+
+/// Exit status for invalid command-line arguments.
+const EXIT_USAGE: i32 = 2;
+/// Exit status when stdin closes before the game is over.
+const EXIT_EOF: i32 = 3;
+/// Exit status for a save file that is corrupted or has been edited.
+const EXIT_DATA: i32 = 65;
+/// Exit status when stdin cannot be read at all.
+const EXIT_IO: i32 = 74;
+
+/// Where `:save` writes the game when no file is named.
+const DEFAULT_SAVE: &str = "guessing-game.save";
+
+/// The language picked with `--lang` or from the locale, set at startup.
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// The player's language, English until it has been set.
+fn lang() -> Lang {
+    LANG.get().copied().unwrap_or_default()
+}
+
+/// Looks `key` up in the player's language and fills in its arguments.
+fn tr(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    lang().format(key, args)
+}
+
+/// Formats a number for the player's locale.
+fn num(n: impl Into<u64>) -> String {
+    lang().number(n)
+}
+
+/// Describes an error or clue from the library in the player's language.
+fn local(value: &impl Localize) -> String {
+    value.localize(lang())
+}
+
+fn error(message: &dyn fmt::Display) -> String {
+    tr("error", &[("message", message)])
+}
+
+fn warning(message: &dyn fmt::Display) -> String {
+    tr("warning", &[("message", message)])
+}
+
+/// The colours picked with `--theme` or a theme file, set at startup.
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Styles a message as `outcome` in the player's theme.
+fn paint(outcome: Outcome, text: &str) -> ColoredString {
+    THEME
+        .get()
+        .copied()
+        .unwrap_or_default()
+        .paint(outcome, text)
+}
+
+/// Turns colour on or off and loads the theme. Colour only depends on the
+/// flags and the environment, so it is settled before anything is printed.
+fn set_appearance(appearance: &Appearance) {
+    colored::control::set_override(appearance.color.enabled());
+
+    let theme = match &appearance.theme {
+        Some(name) => match Theme::named(name) {
+            Some(theme) => Ok(theme),
+            None => Theme::load(Path::new(name)).map_err(|err| (name.into(), err)),
+        },
+        None => match Theme::default_path() {
+            Some(path) if path.exists() => {
+                Theme::load(&path).map_err(|err| (path.display().to_string(), err))
+            }
+            _ => Ok(Theme::DEFAULT),
+        },
+    };
+    match theme {
+        Ok(theme) => {
+            let _ = THEME.set(theme);
+        }
+        Err((name, err)) => {
+            let code = match err.kind() {
+                io::ErrorKind::InvalidData => EXIT_DATA,
+                _ => EXIT_IO,
+            };
+            let message = tr(
+                "theme_failed",
+                &[
+                    ("name", &name),
+                    ("error", &err),
+                    ("themes", &Theme::NAMES.join(", ")),
+                ],
+            );
+            eprintln!("{}", error(&message));
+            process::exit(code);
+        }
+    }
+}
 
 fn main() {
-    println!("Guess the number!");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let _ = LANG.set(cli::requested_lang(&args).unwrap_or_else(Lang::from_env));
+    let (command, appearance) = match cli::parse(args) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}\n\n{}", error(&local(&err)), tr("usage", &[]));
+            process::exit(EXIT_USAGE);
+        }
+    };
+    set_appearance(&appearance);
+
+    let options = match command {
+        Command::Play(options) => *options,
+        Command::Replay { path, speed } => match replay(&path, speed) {
+            Ok(true) => return,
+            Ok(false) => process::exit(1),
+            Err(err) => {
+                let message = tr(
+                    "replay_failed",
+                    &[("path", &path.display()), ("error", &err)],
+                );
+                eprintln!("{}", error(&message));
+                process::exit(EXIT_IO);
+            }
+        },
+        Command::Batch { path } => match run_batch(path.as_deref()) {
+            Ok(0) => return,
+            Ok(failures) => {
+                let message = tr("batch_unreadable", &[("failures", &num(failures as u64))]);
+                eprintln!("{}", error(&message));
+                process::exit(EXIT_DATA);
+            }
+            Err(err) => {
+                eprintln!("{}", error(&tr("batch_failed", &[("error", &err)])));
+                process::exit(EXIT_IO);
+            }
+        },
+        Command::Stats => {
+            print_stats();
+            return;
+        }
+        Command::Ratings { player } => {
+            print_ratings(player.as_deref());
+            return;
+        }
+        Command::Help => {
+            println!("{}", tr("usage", &[]));
+            return;
+        }
+    };
 
-    let secret_number = rand::thread_rng().gen_range(1, 101);
+    if let Some(games) = options.bench {
+        run_bench(&options, games);
+        return;
+    }
 
-    println!("The secret number is: {}", secret_number);
+    if let Some(addr) = &options.serve {
+        if let Err(err) = serve(&options, addr) {
+            eprintln!("{}", error(&err));
+            process::exit(EXIT_IO);
+        }
+        return;
+    }
 
-    println!("Please input your guess.");
+    let result = if options.speedrun {
+        play_speedrun(&options)
+    } else {
+        let mut stdin = io::stdin().lock();
+        match &options.connect {
+            Some(addr) => connect(&options, addr, &mut stdin),
+            None => run(&options, &mut stdin),
+        }
+    };
 
-    loop {
-        let mut guess = String::new();
+    if let Err(err) = result {
+        eprintln!("{}", error(&local(&err)));
+        process::exit(match err {
+            InputError::Eof => EXIT_EOF,
+            InputError::Io(_) => EXIT_IO,
+        });
+    }
+}
+
+fn run<R: BufRead>(options: &Options, input: &mut R) -> Result<(), InputError> {
+    if options.reverse {
+        return play_reverse(options.range(), input);
+    }
+    if options.code {
+        return play_code(options, input);
+    }
+    if options.word {
+        return play_word(options, input);
+    }
+
+    println!("{}", tr("guess_the_number", &[]));
+
+    if let Some(players) = options.players {
+        return play_hotseat(options, players, input);
+    }
+    if options.evil {
+        return play_evil(options, input);
+    }
+    if let Some(lies) = options.lies {
+        return play_lies(options, lies, input);
+    }
+    if let Some(path) = &options.resume {
+        return resume(path, options.insight, input);
+    }
+
+    // The line typed at the prompt goes into a recording's header.
+    let (player, name_line) = match &options.name {
+        Some(name) => (name.clone(), None),
+        None => {
+            let line = ask_name(input)?;
+            (player_name(&line), Some(line))
+        }
+    };
+
+    if options.daily {
+        return play_daily(&player, name_line.as_deref(), options, input);
+    }
+
+    // A recorded game always gets a seed so that it can be replayed.
+    let seed = match &options.record {
+        Some(_) => Some(options.seed.unwrap_or_else(rand::random)),
+        None => options.seed,
+    };
+    let mut game = new_game(options, seed);
+
+    if options.reveal {
+        println!("{}", tr("secret_is", &[("secret", &num(game.secret()))]));
+    }
+    print_hint_help(&game);
+
+    let mut recorder = match (&options.record, seed) {
+        (Some(path), Some(seed)) => {
+            let header = Header::new(seed, &game, name_line.as_deref());
+            Some(Recorder::create(path, &header)?)
+        }
+        _ => None,
+    };
+
+    let session = Session {
+        player: &player,
+        difficulty: options.difficulty_label(),
+        started: Instant::now(),
+        before: Duration::ZERO,
+    };
+    play(
+        &mut game,
+        input,
+        recorder.as_mut(),
+        Some(&session),
+        options.insight,
+    )?;
+    if game.status() != Status::Playing {
+        save_result(&player, options.difficulty_label(), &game);
+        save_rating(&player, &game);
+    }
+    Ok(())
+}
+
+/// Continues a game saved with `:save`. The save file is removed once the
+/// game is over.
+fn resume<R: BufRead>(path: &Path, insight: bool, input: &mut R) -> Result<(), InputError> {
+    let loaded = SaveFile::load(path).and_then(|save| save.game().map(|game| (save, game)));
+    let (save, mut game) = match loaded {
+        Ok(loaded) => loaded,
+        Err(err) => {
+            let message = tr(
+                "resume_failed",
+                &[("path", &path.display()), ("error", &local(&err))],
+            );
+            eprintln!("{}", error(&message));
+            process::exit(EXIT_DATA);
+        }
+    };
+
+    println!(
+        "{}",
+        tr(
+            "welcome_back",
+            &[
+                ("player", &save.player),
+                ("time", &format_duration(save.elapsed()))
+            ]
+        )
+    );
+    for &guess in game.history() {
+        let guess_arg: &[(&str, &dyn fmt::Display)] = &[("guess", &num(guess))];
+        let message = match GuessOutcome::from(guess.cmp(&game.secret())) {
+            GuessOutcome::TooBig => tr("was_too_big", guess_arg),
+            _ => tr("was_too_small", guess_arg),
+        };
+        println!("{}", message);
+    }
+
+    let session = Session {
+        player: &save.player,
+        difficulty: &save.difficulty,
+        started: Instant::now(),
+        before: save.elapsed(),
+    };
+    play(&mut game, input, None, Some(&session), insight)?;
+    if game.status() != Status::Playing {
+        save_result(&save.player, &save.difficulty, &game);
+        save_rating(&save.player, &game);
+        let _ = fs::remove_file(path);
+    }
+    Ok(())
+}
+
+/// Who is playing a single-player game and for how long, so that `:save`
+/// can write it out.
+struct Session<'a> {
+    player: &'a str,
+    difficulty: &'a str,
+    started: Instant,
+    /// Time played before the game was last resumed.
+    before: Duration,
+}
+
+impl Session<'_> {
+    fn elapsed(&self) -> Duration {
+        self.before + self.started.elapsed()
+    }
+}
+
+/// Formats a duration as minutes and seconds, e.g. `2m 05s`.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    tr(
+        "duration",
+        &[
+            ("minutes", &(seconds / 60)),
+            ("seconds", &format!("{:02}", seconds % 60)),
+        ],
+    )
+}
+
+/// Formats seconds to a tenth, e.g. `2.5s`.
+fn format_seconds(seconds: f64) -> String {
+    tr("seconds", &[("seconds", &format!("{:.1}", seconds))])
+}
+
+/// Plays against the clock. Input is read on a separate thread so that the
+/// time limit can end the game while the player is still thinking.
+fn play_speedrun(options: &Options) -> Result<(), InputError> {
+    println!("{}", tr("guess_the_number", &[]));
+    let player = match &options.name {
+        Some(name) => name.clone(),
+        None => player_name(&ask_name(&mut io::stdin().lock())?),
+    };
+    let lines = read_lines();
+
+    let mut run = Speedrun::new(new_game(options, options.seed), options.time_limit());
+    if options.reveal {
+        println!(
+            "{}",
+            tr("secret_is", &[("secret", &num(run.game().secret()))])
+        );
+    }
+    print_hint_help(run.game());
+    let range = run.game().range().clone();
+    print_please_guess(run.game().range(), run.game().attempts_left());
+    match run.limit() {
+        Some(limit) => println!(
+            "{}",
+            tr("clock_running_limit", &[("limit", &format_duration(limit))])
+        ),
+        None => println!("{}", tr("clock_running", &[])),
+    }
+
+    let started = Instant::now();
+    let deadline = run.limit().map(|limit| started + limit);
+    while run.status() == Status::Playing {
+        let prompted = Instant::now();
+        let line = {
+            print!("[{}] > ", speedrun::clock(started.elapsed()));
+            io::stdout().flush()?;
+            let _ticker = Ticker::start(started);
+            let received = match deadline {
+                Some(deadline) => lines.recv_timeout(deadline.saturating_duration_since(prompted)),
+                None => lines.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
+                Ok(line) => Some(line?),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => return Err(InputError::Eof),
+            }
+        };
+        let Some(line) = line.filter(|_| deadline.is_none_or(|deadline| Instant::now() < deadline))
+        else {
+            run.time_up();
+            println!(
+                "\n{} {}",
+                paint(Outcome::Lose, &tr("times_up", &[])),
+                paint(
+                    Outcome::Lose,
+                    &tr("number_was", &[("secret", &num(run.game().secret()))])
+                )
+            );
+            break;
+        };
+        let turn = run.take_turn(&line, prompted.elapsed());
+        print_turn(run.game(), &turn, options.insight);
+    }
+
+    let elapsed = started.elapsed();
+    let best = run.best(elapsed);
+    if let Some(best) = best {
+        println!(
+            "{}",
+            tr(
+                "speedrun_time",
+                &[
+                    ("time", &speedrun::clock(elapsed)),
+                    ("par", &speedrun::clock(speedrun::par(&range))),
+                    ("score", &num(best.score)),
+                ]
+            )
+        );
+    }
+    if !run.latencies().is_empty() {
+        let times: Vec<String> = run
+            .latencies()
+            .iter()
+            .map(|latency| format_seconds(latency.as_secs_f64()))
+            .collect();
+        let total: Duration = run.latencies().iter().sum();
+        let average = total.as_secs_f64() / run.latencies().len() as f64;
+        println!(
+            "{}",
+            tr(
+                "guess_times",
+                &[
+                    ("times", &times.join(", ")),
+                    ("average", &format_seconds(average)),
+                ]
+            )
+        );
+    }
+
+    save_rating(&player, run.game());
+    let Some(path) = Leaderboard::default_path() else {
+        eprintln!("{}", warning(&tr("no_data_dir", &[])));
+        return Ok(());
+    };
+    let difficulty = options.difficulty_label();
+    match Leaderboard::record_speedrun(&path, &player, difficulty, run.game().attempts(), best) {
+        Ok(earlier) => {
+            if best.is_some_and(|best| earlier.is_none_or(|earlier| best.score > earlier.score)) {
+                println!("{}", paint(Outcome::Win, &tr("new_personal_best", &[])));
+            } else if let Some(earlier) = earlier {
+                println!(
+                    "{}",
+                    tr(
+                        "personal_best",
+                        &[
+                            ("difficulty", &difficulty),
+                            ("score", &num(earlier.score)),
+                            (
+                                "time",
+                                &speedrun::clock(Duration::from_millis(earlier.time_ms))
+                            ),
+                        ]
+                    )
+                );
+            }
+            award_achievements(&path, &player, difficulty, run.game());
+        }
+        Err(err) => eprintln!("{}", warning(&result_not_saved(&path, &err))),
+    }
+    Ok(())
+}
+
+/// Reads stdin line by line on its own thread, stopping after the first
+/// error or the end of input.
+fn read_lines() -> Receiver<Result<String, InputError>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut stdin = io::stdin().lock();
+        loop {
+            let line = guessing_game::read_line(&mut stdin);
+            let done = line.is_err();
+            if sender.send(line).is_err() || done {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+/// Keeps the stopwatch at the start of the prompt ticking while the player
+/// types. It only runs on a terminal, where the cursor can be moved back.
+struct Ticker {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Ticker {
+    fn start(started: Instant) -> Ticker {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = io::stdout().is_terminal().then(|| {
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                loop {
+                    thread::sleep(Duration::from_millis(100));
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    // Save the cursor, redraw the clock and put the cursor back.
+                    print!("\x1b7\r[{}]\x1b8", speedrun::clock(started.elapsed()));
+                    let _ = io::stdout().flush();
+                }
+            })
+        });
+        Ticker { stop, thread }
+    }
+}
+
+impl Drop for Ticker {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Plays today's puzzle, unless this player has already started it.
+/// `name_line` is what the player typed at the name prompt, if anything.
+fn play_daily<R: BufRead>(
+    player: &str,
+    name_line: Option<&str>,
+    options: &Options,
+    input: &mut R,
+) -> Result<(), InputError> {
+    let puzzle = Puzzle::today();
+    let date = puzzle.date.to_string();
+    let path = Leaderboard::default_path();
+    match path
+        .as_deref()
+        .map(|path| Leaderboard::start_daily(path, player, &date))
+    {
+        Some(Ok(Some(earlier))) => {
+            println!("{}", tr("already_played", &[("number", &puzzle.number())]));
+            if earlier.finished {
+                println!("{}", earlier.share);
+            }
+            return Ok(());
+        }
+        Some(Ok(None)) => {}
+        Some(Err(err)) => eprintln!("{}", warning(&tr("daily_check_failed", &[("error", &err)]))),
+        None => eprintln!("{}", warning(&tr("no_data_dir_daily", &[]))),
+    }
+
+    println!(
+        "{}",
+        tr(
+            "daily_challenge",
+            &[("number", &puzzle.number()), ("date", &date)]
+        )
+    );
+    let mut game = puzzle.game();
+    let mut recorder = match &options.record {
+        Some(path) => {
+            let header = Header::new(puzzle.seed(), &game, name_line);
+            Some(Recorder::create(path, &header)?)
+        }
+        None => None,
+    };
+    play(&mut game, input, recorder.as_mut(), None, options.insight)?;
+
+    let share = puzzle.share_line(&game);
+    let result = DailyResult {
+        finished: true,
+        won: game.status() == Status::Won,
+        attempts: game.attempts(),
+        share: share.clone(),
+    };
+    if let Some(path) = path {
+        match Leaderboard::finish_daily(&path, player, &date, result) {
+            Ok(_) => award_achievements(&path, player, "daily", &game),
+            Err(err) => eprintln!("{}", warning(&result_not_saved(&path, &err))),
+        }
+    }
+    save_rating(player, &game);
+    println!("{}", tr("share_result", &[]));
+    println!("{}", share);
+    Ok(())
+}
+
+/// Asks for the player's name and returns the line as typed.
+fn ask_name<R: BufRead>(input: &mut R) -> Result<String, InputError> {
+    println!("{}", tr("whats_your_name", &[]));
+    guessing_game::read_line(input)
+}
+
+/// The name a line typed at the name prompt stands for.
+fn player_name(line: &str) -> String {
+    let name = line.trim();
+    // Not translated: it is saved as the player's name.
+    if name.is_empty() { "anonymous" } else { name }.to_string()
+}
+
+/// Plays until the game is over or, if there is a session to save, the
+/// player types `:save [file]`.
+fn play<R: BufRead>(
+    game: &mut Game,
+    input: &mut R,
+    mut recorder: Option<&mut Recorder>,
+    session: Option<&Session>,
+    insight: bool,
+) -> Result<(), InputError> {
+    print_please_guess(game.range(), game.attempts_left());
+
+    while game.status() == Status::Playing {
+        let line = guessing_game::read_line(input)?;
+        if let Some(file) = line.trim().strip_prefix(":save") {
+            // The game never sees the line, but logging it lets a replay
+            // tell a saved session from one that was cut off.
+            if let Some(recorder) = recorder.as_deref_mut() {
+                recorder.record(&line, None)?;
+            }
+            let Some(session) = session else {
+                println!("{}", paint(Outcome::Error, &tr("cannot_save", &[])));
+                continue;
+            };
+            let path = Path::new(match file.trim() {
+                "" => DEFAULT_SAVE,
+                file => file,
+            });
+            let save = SaveFile::new(game, session.player, session.difficulty, session.elapsed());
+            match save.write(path) {
+                Ok(()) => {
+                    println!("{}", tr("game_saved", &[("path", &path.display())]));
+                    return Ok(());
+                }
+                Err(err) => {
+                    let message = tr(
+                        "could_not_save",
+                        &[("path", &path.display()), ("error", &err)],
+                    );
+                    println!("{}", paint(Outcome::Error, &message));
+                }
+            }
+            continue;
+        }
+        let turn = game.take_turn(&line);
+        if let Some(recorder) = recorder.as_deref_mut() {
+            recorder.record(&line, Some(&turn))?;
+        }
+        print_turn(game, &turn, insight);
+    }
+    Ok(())
+}
+
+/// Colors that tell hot-seat players apart without clashing with the red
+/// and green used for outcomes.
+const PLAYER_COLORS: [Color; 8] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Yellow,
+    Color::Blue,
+    Color::BrightCyan,
+    Color::BrightMagenta,
+    Color::BrightYellow,
+    Color::BrightBlue,
+];
+
+/// Several players take turns at one terminal to find the same secret.
+fn play_hotseat<R: BufRead>(
+    options: &Options,
+    players: usize,
+    input: &mut R,
+) -> Result<(), InputError> {
+    let mut names: Vec<String> = Vec::with_capacity(players);
+    for number in 1..=players {
+        // Results and ratings are saved by name, so two players sharing one
+        // would be merged into a single record.
+        let name = loop {
+            println!("{}", tr("player_name", &[("number", &number)]));
+            let name = guessing_game::read_line(input)?;
+            let name = name.trim();
+            // Not translated, like `anonymous`: it is saved as the player's name.
+            let name = if name.is_empty() {
+                format!("Player {}", number)
+            } else {
+                name.to_string()
+            };
+            if !names.contains(&name) {
+                break name;
+            }
+            println!(
+                "{}",
+                paint(Outcome::Error, &tr("name_taken", &[("name", &name)]))
+            );
+        };
+        names.push(name);
+    }
+
+    let range = options.range();
+    let game = new_game(options, options.seed);
+    let secret = game.secret();
+    if options.reveal {
+        println!("{}", tr("secret_is", &[("secret", &num(secret))]));
+    }
+    print_hint_help(&game);
+
+    let mut hotseat =
+        HotSeat::new(names, game).expect("player count is checked when parsing arguments");
+    let colored_name =
+        |i: usize, name: &str| name.color(PLAYER_COLORS[i % PLAYER_COLORS.len()]).bold();
+
+    println!(
+        "{}",
+        tr(
+            "everyone_guess",
+            &[
+                ("min", &num(*range.start())),
+                ("max", &num(*range.end())),
+                ("attempts", &num(options.attempts())),
+            ]
+        )
+    );
+
+    while let Some(current) = hotseat.current() {
+        let seat = &hotseat.seats()[current];
+        println!(
+            "{}",
+            tr(
+                "your_guess",
+                &[("player", &colored_name(current, &seat.name))]
+            )
+        );
+        let line = guessing_game::read_line(input)?;
+        let turn = hotseat.take_turn(&line);
+        print_turn(&hotseat.seats()[current].game, &turn, options.insight);
+    }
+
+    match hotseat.winner() {
+        Some(winner) => println!(
+            "{}",
+            tr(
+                "player_found",
+                &[
+                    ("winner", &paint(Outcome::Win, &tr("winner", &[]))),
+                    (
+                        "player",
+                        &colored_name(winner, &hotseat.seats()[winner].name)
+                    ),
+                    ("secret", &num(secret)),
+                ]
+            )
+        ),
+        None => println!(
+            "{}",
+            paint(
+                Outcome::Lose,
+                &tr("nobody_found", &[("secret", &num(secret))])
+            )
+        ),
+    }
+
+    println!(
+        "{:<16} {:>8} {:>8}  {}",
+        tr("column_player", &[]),
+        tr("column_attempts", &[]),
+        tr("column_closest", &[]),
+        tr("column_result", &[])
+    );
+    for (i, seat) in hotseat.seats().iter().enumerate() {
+        let result = match seat.game.status() {
+            Status::Won => tr("seat_won", &[]),
+            Status::Lost => tr("seat_out", &[]),
+            Status::Playing => tr("seat_playing", &[]),
+        };
+        let closest = seat
+            .closest()
+            .map_or("-".to_string(), |d| format!("±{}", num(d)));
+        println!(
+            "{} {:>8} {:>8}  {}",
+            colored_name(i, &format!("{:<16}", seat.name)),
+            num(seat.game.attempts()),
+            closest,
+            result
+        );
+        save_result(&seat.name, options.difficulty_label(), &seat.game);
+    }
+
+    let places: Vec<(String, u32)> = hotseat
+        .seats()
+        .iter()
+        .map(|seat| {
+            let place = if seat.game.status() == Status::Won {
+                1
+            } else {
+                2
+            };
+            (seat.name.clone(), place)
+        })
+        .collect();
+    save_match(MatchKind::HotSeat, &places);
+    Ok(())
+}
+
+/// A single-player game set up from the command line.
+fn new_game(options: &Options, seed: Option<u64>) -> Game {
+    let range = options.range();
+    match seed {
+        Some(seed) => Game::seeded(seed, range),
+        None => Game::random(range),
+    }
+    .with_max_attempts(options.attempts())
+    .with_hints(options.hints())
+}
+
+fn print_hint_help(game: &Game) {
+    let requestable: Vec<String> = game
+        .hints()
+        .iter()
+        .filter(|kind| **kind != HintKind::Temperature)
+        .map(|kind| tr("hint_cost", &[("kind", kind), ("cost", &num(kind.cost()))]))
+        .collect();
+    if !requestable.is_empty() {
+        println!(
+            "{}",
+            tr("hints_on_offer", &[("hints", &requestable.join(", "))])
+        );
+    }
+}
+
+fn print_please_guess(range: &RangeInclusive<u32>, attempts: Option<u32>) {
+    println!(
+        "{}",
+        tr(
+            "please_guess",
+            &[
+                ("min", &num(*range.start())),
+                ("max", &num(*range.end())),
+                ("attempts", &num(attempts.unwrap_or(0))),
+            ]
+        )
+    );
+}
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
+/// Prints the game's response to one line of input.
+fn print_turn(game: &Game, turn: &Turn, insight: bool) {
+    print_feedback(game, turn);
+    if insight && let Turn::Guessed { .. } = turn {
+        print_insight(game);
+    }
+}
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+/// Shows what the latest guess taught the player, in bits, next to what a
+/// bisection is sure to learn from the same position.
+fn print_insight(game: &Game) {
+    let steps = insight::steps(game);
+    let Some(step) = steps.last() else {
+        return;
+    };
+    if game.status() == Status::Playing {
+        let message = match step.after() {
+            1 => tr(
+                "only_possible",
+                &[("number", &num(*step.remaining.start()))],
+            ),
+            after => tr(
+                "still_possible",
+                &[
+                    ("low", &num(*step.remaining.start())),
+                    ("high", &num(*step.remaining.end())),
+                    ("count", &num(after)),
+                ],
+            ),
         };
+        println!("{}", message);
+    }
+    let efficiency = insight::efficiency(&steps).map_or(String::new(), |efficiency| {
+        let percent: &[(&str, &dyn fmt::Display)] =
+            &[("percent", &format!("{:.0}", efficiency * 100.0))];
+        match game.status() {
+            Status::Playing => format!(" {}", tr("efficiency_so_far", percent)),
+            _ => format!(" {}", tr("efficiency_overall", percent)),
+        }
+    });
+    println!(
+        "{}{}",
+        tr(
+            "bits_gained",
+            &[
+                ("bits", &format!("{:.2}", step.bits())),
+                ("ideal", &format!("{:.2}", step.ideal_bits())),
+            ]
+        ),
+        efficiency
+    );
+}
+
+fn print_feedback(game: &Game, turn: &Turn) {
+    let (guess, outcome) = match turn {
+        Turn::Hint(Ok(hint)) => {
+            println!("{}", paint(Outcome::Hint, &local(hint)));
+            return;
+        }
+        Turn::Hint(Err(err)) => {
+            println!("{}", paint(Outcome::Error, &local(err)));
+            return;
+        }
+        Turn::Invalid(err) => {
+            println!("{}", paint(Outcome::Error, &local(err)));
+            return;
+        }
+        Turn::Rejected { guess, error } => {
+            println!("{}", tr("you_guessed", &[("guess", &num(*guess))]));
+            println!("{}", paint(Outcome::Error, &local(error)));
+            return;
+        }
+        Turn::Guessed { guess, outcome } => (guess, outcome),
+    };
+
+    println!("{}", tr("you_guessed", &[("guess", &num(*guess))]));
+
+    let hint = match outcome {
+        GuessOutcome::Win => {
+            println!(
+                "{} {}",
+                paint(Outcome::Win, &tr("you_win", &[])),
+                tr("score", &[("score", &num(game.score()))])
+            );
+            return;
+        }
+        GuessOutcome::TooBig => paint(Outcome::TooHigh, &tr("too_big", &[])),
+        GuessOutcome::TooSmall => paint(Outcome::TooLow, &tr("too_small", &[])),
+    };
+
+    if game.status() == Status::Lost {
+        println!(
+            "{} {}",
+            hint,
+            paint(
+                Outcome::Lose,
+                &tr("you_lose", &[("secret", &num(game.secret()))])
+            )
+        );
+        return;
+    }
+
+    match game.attempts_left() {
+        Some(1) => println!("{} {}", hint, tr("one_try_left", &[])),
+        Some(left) => println!("{} {}", hint, tr("tries_left", &[("left", &num(left))])),
+        None => println!("{}", hint),
+    }
+
+    // Warmer/colder is free, so it is shown after every guess.
+    if game.hints().contains(&HintKind::Temperature)
+        && let Some(temperature) = hints::hint(game, HintKind::Temperature)
+    {
+        println!("{}", paint(Outcome::Hint, &local(&temperature)));
+    }
+}
+
+/// Plays scripted games and prints a JSON report for each. Returns how
+/// many games could not be read.
+fn run_batch(path: Option<&Path>) -> io::Result<usize> {
+    let out = io::stdout().lock();
+    match path {
+        Some(path) => batch::run(io::BufReader::new(fs::File::open(path)?), out),
+        None => batch::run(io::stdin().lock(), out),
+    }
+}
+
+/// Plays a session log back with its original timing divided by `speed`,
+/// up to the first turn the current rules no longer agree with. Returns
+/// whether the log still matches.
+fn replay(path: &Path, speed: f64) -> io::Result<bool> {
+    let recording = Recording::load(path)?;
+    let header = &recording.header;
+    let divergence = recording.verify();
+    let shown = divergence
+        .as_ref()
+        .map_or(recording.entries.len(), |(i, _)| i + 1);
+    let mut game = header.game();
+
+    println!(
+        "{}",
+        tr(
+            "replaying",
+            &[
+                ("path", &path.display()),
+                ("seed", &header.seed),
+                ("min", &num(header.min)),
+                ("max", &num(header.max)),
+            ]
+        )
+    );
+
+    if let Some(line) = &header.name_line {
+        println!("{}", tr("whats_your_name", &[]));
+        print_input(line);
+    }
+
+    let mut previous = 0;
+    for entry in &recording.entries[..shown] {
+        if speed > 0.0 {
+            let wait = entry.at_ms.saturating_sub(previous) as f64 / speed;
+            thread::sleep(Duration::from_secs_f64(wait / 1000.0));
+        }
+        previous = entry.at_ms;
 
-        println!("You guessed: {}", guess);
+        print_input(&entry.input);
+        if entry.turn.is_some() {
+            let turn = game.take_turn(&entry.input);
+            print_turn(&game, &turn, false);
+        }
+    }
+
+    if let Some((i, turn)) = divergence {
+        let expected = recording.entries[i]
+            .turn
+            .as_ref()
+            .expect("verify only stops at played lines");
+        let message = tr(
+            "replay_diverged",
+            &[
+                ("line", &(i + 1)),
+                ("expected", &format!("{:?}", expected)),
+                ("got", &format!("{:?}", turn)),
+            ],
+        );
+        eprintln!("{}", error(&message));
+        return Ok(false);
+    }
+
+    if game.status() == Status::Playing && recording.ends_unplayed() {
+        println!("{}", tr("replay_saved", &[]));
+    }
+    println!("{}", paint(Outcome::Win, &tr("replay_matches", &[])));
+    Ok(true)
+}
+
+/// Echoes a recorded line the way the player typed it.
+fn print_input(line: &str) {
+    print!("> {}", line);
+    if !line.ends_with('\n') {
+        println!();
+    }
+}
 
-        match guess.cmp(&secret_number) {
-            Ordering::Equal => {
-                println!("{}", "You win!".green());
+/// The computer guesses a number the player has picked, bisecting the
+/// interval that is still consistent with the player's answers.
+fn play_reverse<R: BufRead>(range: RangeInclusive<u32>, input: &mut R) -> Result<(), InputError> {
+    println!(
+        "{}",
+        tr(
+            "think_of_number",
+            &[("min", &num(*range.start())), ("max", &num(*range.end()))]
+        )
+    );
+    println!("{}", tr("answer_with", &[]));
+
+    let mut guesser = Guesser::new(range);
+
+    while let Some(guess) = guesser.next_guess() {
+        println!("{}", tr("is_it", &[("guess", &num(guess))]));
+
+        let reply = loop {
+            let line = guessing_game::read_line(input)?;
+            match reverse::parse_answer(&line) {
+                Some(reply) => break reply,
+                None => println!("{}", paint(Outcome::Error, &tr("please_answer", &[]))),
+            }
+        };
+
+        if let Err(contradiction) = guesser.answer(guess, reply) {
+            println!(
+                "{}",
+                paint(
+                    Outcome::Error,
+                    &tr("cheating", &[("contradiction", &local(&contradiction))])
+                )
+            );
+            return Ok(());
+        }
+    }
+
+    println!(
+        "{} {}",
+        paint(Outcome::Win, &tr("got_it", &[])),
+        tr(
+            "your_number_is",
+            &[
+                ("number", &num(guesser.solution().unwrap_or_default())),
+                ("guesses", &guesser.answers().len()),
+            ]
+        )
+    );
+    Ok(())
+}
+
+/// Plays against a host that keeps its options open as long as it can.
+fn play_evil<R: BufRead>(options: &Options, input: &mut R) -> Result<(), InputError> {
+    let range = options.range();
+    let mut host = EvilHost::new(range.clone()).with_max_attempts(options.attempts());
+    print_please_guess(&range, Some(options.attempts()));
+    println!("{}", tr("not_picked", &[]));
+
+    while host.status() == Status::Playing {
+        let (guess, outcome) = read_guess(input, |line| host.take_turn(line))?;
+        println!("{}", tr("you_guessed", &[("guess", &num(guess))]));
+        let hint = match outcome {
+            GuessOutcome::Win => {
+                let left = host.attempts_left().unwrap_or(0);
+                println!(
+                    "{} {} {}",
+                    paint(Outcome::Win, &tr("you_win", &[])),
+                    tr("cornered", &[("guesses", &host.attempts())]),
+                    tr(
+                        "score",
+                        &[("score", &num(guessing_game::score(&range, left)))]
+                    )
+                );
+                return Ok(());
+            }
+            GuessOutcome::TooBig => paint(Outcome::TooHigh, &tr("too_big", &[])),
+            GuessOutcome::TooSmall => paint(Outcome::TooLow, &tr("too_small", &[])),
+        };
+
+        let candidates = host.candidates();
+        let possible = u64::from(*candidates.end() - *candidates.start()) + 1;
+        match host.secret() {
+            Some(secret) => println!(
+                "{} {}",
+                hint,
+                paint(Outcome::Lose, &tr("you_lose", &[("secret", &num(secret))]))
+            ),
+            None => print_narrowed(hint, host.attempts_left(), possible),
+        }
+    }
+    Ok(())
+}
+
+/// Plays against a host that may lie in up to `lies` of its answers.
+fn play_lies<R: BufRead>(options: &Options, lies: u32, input: &mut R) -> Result<(), InputError> {
+    let range = options.range();
+    let attempts = options
+        .attempts
+        .unwrap_or_else(|| ulam::attempts_needed(&range, lies));
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut host = LyingHost::seeded(seed, range.clone(), lies).with_max_attempts(attempts);
+    if options.reveal {
+        println!("{}", tr("secret_is", &[("secret", &num(host.secret()))]));
+    }
+    print_please_guess(&range, Some(attempts));
+    match lies {
+        1 => println!("{}", tr("may_lie_once", &[])),
+        n => println!("{}", tr("may_lie", &[("lies", &n)])),
+    }
+
+    while host.status() == Status::Playing {
+        let (guess, outcome) = read_guess(input, |line| host.take_turn(line))?;
+        println!("{}", tr("you_guessed", &[("guess", &num(guess))]));
+        let hint = match outcome {
+            GuessOutcome::Win => {
+                let left = host.attempts_left().unwrap_or(0);
+                println!(
+                    "{} {}",
+                    paint(Outcome::Win, &tr("you_win", &[])),
+                    tr(
+                        "score",
+                        &[("score", &num(guessing_game::score(&range, left)))]
+                    )
+                );
                 break;
             }
-            Ordering::Greater => println!("{}", "Too big!".red()),
-            Ordering::Less => println!("{}", "Too small!".red()),
+            GuessOutcome::TooBig => paint(Outcome::TooHigh, &tr("too_big", &[])),
+            GuessOutcome::TooSmall => paint(Outcome::TooLow, &tr("too_small", &[])),
+        };
+
+        if host.status() == Status::Lost {
+            println!(
+                "{} {}",
+                hint,
+                paint(
+                    Outcome::Lose,
+                    &tr("you_lose", &[("secret", &num(host.secret()))])
+                )
+            );
+        } else {
+            print_narrowed(hint, host.attempts_left(), host.tracker().remaining());
+        }
+    }
+
+    let lies_told = host.lies_told();
+    println!("{}", tr("my_answers", &[]));
+    for (index, answer) in host.tracker().answers().iter().enumerate() {
+        if lies_told.contains(&index) {
+            println!(
+                "  #{} {} {}",
+                index + 1,
+                local(answer),
+                paint(Outcome::Error, &tr("a_lie", &[]))
+            );
+        } else {
+            println!("  #{} {}", index + 1, local(answer));
+        }
+    }
+    Ok(())
+}
+
+/// Plays bulls and cows, with the solver on hand for hints.
+fn play_code<R: BufRead>(options: &Options, input: &mut R) -> Result<(), InputError> {
+    let rules = options.code_rules();
+    let attempts = options
+        .attempts
+        .unwrap_or_else(|| mastermind::Rules::attempts(options.difficulty));
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut game = CodeGame::seeded(seed, rules).with_max_attempts(attempts);
+    if options.reveal {
+        println!("{}", tr("secret_code", &[("code", game.secret())]));
+    }
+    println!(
+        "{}",
+        tr(
+            "crack_the_code",
+            &[("rules", &local(&rules)), ("attempts", &num(attempts))]
+        )
+    );
+    println!("{}", tr("bulls_and_cows", &[]));
+
+    let mut solver = mastermind::Solver::new(&rules);
+    while game.status() == Status::Playing {
+        let line = guessing_game::read_line(input)?;
+        if line.trim() == "hint" {
+            let code = solver.next_guess();
+            let hint = match solver.candidates().len() {
+                1 => tr("one_code_possible", &[("code", &code)]),
+                possible => tr(
+                    "codes_possible",
+                    &[("count", &num(possible as u64)), ("code", &code)],
+                ),
+            };
+            println!("{}", paint(Outcome::Hint, &hint));
+            continue;
+        }
+
+        let (guess, score) = match game.take_turn(&line) {
+            CodeTurn::Scored { guess, score } => (guess, score),
+            CodeTurn::Invalid(err) => {
+                println!("{}", paint(Outcome::Error, &local(&err)));
+                continue;
+            }
+            CodeTurn::Rejected(err) => {
+                println!("{}", paint(Outcome::Error, &local(&err)));
+                continue;
+            }
+        };
+        solver.answer(&guess, score);
+
+        println!("{}", tr("you_guessed", &[("guess", &guess)]));
+        let feedback = paint(Outcome::Miss, &local(&score));
+        match (game.status(), game.attempts_left()) {
+            (Status::Won, _) => println!(
+                "{} {}",
+                paint(Outcome::Win, &tr("you_win", &[])),
+                tr("cracked_in", &[("guesses", &game.attempts())])
+            ),
+            (Status::Lost, _) => println!(
+                "{} {}",
+                feedback,
+                paint(Outcome::Lose, &tr("code_was", &[("code", game.secret())]))
+            ),
+            (_, left) => println!(
+                "{}",
+                tr(
+                    "feedback_and_tries",
+                    &[("feedback", &feedback), ("tries", &tries_left(left))]
+                )
+            ),
+        }
+    }
+
+    let best = mastermind::solve(game.secret(), &rules, u32::MAX).len();
+    println!("{}", tr("solver_cracks", &[("guesses", &best)]));
+    Ok(())
+}
+
+/// Plays word mode, showing each guess in the colors of its marks.
+fn play_word<R: BufRead>(options: &Options, input: &mut R) -> Result<(), InputError> {
+    let attempts = options.attempts.unwrap_or(wordle::ATTEMPTS);
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut game = WordGame::seeded(seed).with_max_attempts(attempts);
+    if options.hard_mode {
+        game = game.with_hard_mode();
+    }
+    if options.reveal {
+        println!("{}", tr("secret_word", &[("word", &game.secret())]));
+    }
+    println!(
+        "{}",
+        tr(
+            "guess_the_word",
+            &[("length", &wordle::LENGTH), ("attempts", &num(attempts))]
+        )
+    );
+    if game.is_hard() {
+        println!("{}", tr("hard_mode", &[]));
+    }
+
+    while game.status() == Status::Playing {
+        let line = guessing_game::read_line(input)?;
+        let (guess, marks) = match game.take_turn(&line) {
+            WordTurn::Marked { guess, marks } => (guess, marks),
+            WordTurn::Invalid(err) => {
+                println!("{}", paint(Outcome::Error, &local(&err)));
+                continue;
+            }
+        };
+
+        let theme = THEME.get().copied().unwrap_or_default();
+        let letters: String = guess
+            .chars()
+            .zip(marks)
+            .map(|(letter, mark)| {
+                let tile = format!(" {} ", letter.to_ascii_uppercase());
+                theme.tile(mark, &tile).to_string()
+            })
+            .collect();
+        let squares: String = marks.iter().map(Mark::to_string).collect();
+        println!("{} {}", letters, squares);
+
+        match (game.status(), game.attempts_left()) {
+            (Status::Won, _) => println!(
+                "{} {}",
+                paint(Outcome::Win, &tr("you_win", &[])),
+                tr("found_in", &[("guesses", &game.attempts())])
+            ),
+            (Status::Lost, _) => println!(
+                "{}",
+                paint(Outcome::Lose, &tr("word_was", &[("word", &game.secret())]))
+            ),
+            (_, left) => println!("{}", tries_left(left)),
+        }
+    }
+    Ok(())
+}
+
+/// Reads lines until one of them is a guess that `take_turn` accepts,
+/// reporting the ones it does not.
+fn read_guess<R: BufRead>(
+    input: &mut R,
+    mut take_turn: impl FnMut(&str) -> Turn,
+) -> Result<(u32, GuessOutcome), InputError> {
+    loop {
+        let line = guessing_game::read_line(input)?;
+        match take_turn(&line) {
+            Turn::Guessed { guess, outcome } => return Ok((guess, outcome)),
+            Turn::Rejected { guess, error } => {
+                println!("{}", tr("you_guessed", &[("guess", &num(guess))]));
+                println!("{}", paint(Outcome::Error, &local(&error)));
+            }
+            Turn::Invalid(err) => println!("{}", paint(Outcome::Error, &local(&err))),
+            Turn::Hint(Ok(hint)) => println!("{}", paint(Outcome::Hint, &local(&hint))),
+            Turn::Hint(Err(err)) => println!("{}", paint(Outcome::Error, &local(&err))),
+        }
+    }
+}
+
+/// Reports a wrong guess along with how much room is left.
+fn print_narrowed(hint: ColoredString, attempts_left: Option<u32>, possible: u64) {
+    let possible = match possible {
+        1 => tr("one_number", &[]),
+        n => tr("numbers", &[("count", &num(n))]),
+    };
+    let narrowed = match attempts_left {
+        Some(1) => tr("one_try_left_possible", &[("possible", &possible)]),
+        Some(left) => tr(
+            "tries_left_possible",
+            &[("left", &num(left)), ("possible", &possible)],
+        ),
+        None => tr("still_in_play", &[("possible", &possible)]),
+    };
+    println!("{} {}", hint, narrowed);
+}
+
+/// How many tries are left, when there is a limit.
+fn tries_left(attempts_left: Option<u32>) -> String {
+    match attempts_left {
+        Some(1) => tr("one_try_left", &[]),
+        left => tr("tries_left", &[("left", &num(left.unwrap_or_default()))]),
+    }
+}
+
+/// Hosts a multiplayer round and follows it on stdout.
+fn serve(options: &Options, addr: &str) -> io::Result<()> {
+    let range = options.range();
+    let game = match options.seed {
+        Some(seed) => Game::seeded(seed, range.clone()),
+        None => Game::random(range.clone()),
+    };
+    if options.reveal {
+        println!("{}", tr("secret_is", &[("secret", &num(game.secret()))]));
+    }
+
+    let server = net::Server::bind(addr, game)?;
+    println!(
+        "{}",
+        tr(
+            "hosting",
+            &[
+                ("addr", &server.local_addr()?),
+                ("min", &num(*range.start())),
+                ("max", &num(*range.end())),
+            ]
+        )
+    );
+    let winner = server.run(|event| println!("{}", describe(event)))?;
+    let places: Vec<(String, u32)> = std::iter::once((winner.name, 1))
+        .chain(winner.others.into_iter().map(|name| (name, 2)))
+        .collect();
+    save_match(MatchKind::Network, &places);
+    Ok(())
+}
+
+/// Joins a multiplayer round, sending guesses from `input` while a second
+/// thread prints what the other players are doing.
+fn connect<R: BufRead>(options: &Options, addr: &str, input: &mut R) -> Result<(), InputError> {
+    let name = match &options.name {
+        Some(name) => name.clone(),
+        None => player_name(&ask_name(input)?),
+    };
+    let (mut client, (min, max)) = net::Client::join(addr, &name)?;
+    println!(
+        "{}",
+        tr(
+            "joined_as",
+            &[("name", &name), ("min", &num(min)), ("max", &num(max))]
+        )
+    );
+
+    let mut sender = client.sender()?;
+    thread::spawn(move || {
+        loop {
+            match client.receive() {
+                Ok(Some(message)) => println!("{}", describe(&message)),
+                Ok(None) => process::exit(0),
+                Err(err) => {
+                    eprintln!("{}", error(&err));
+                    process::exit(EXIT_IO);
+                }
+            }
+        }
+    });
+
+    loop {
+        let line = guessing_game::read_line(input)?;
+        match guessing_game::parse_guess(&line) {
+            Ok(guess) => writeln!(sender, "{}", ClientMessage::Guess(guess))?,
+            Err(err) => println!("{}", paint(Outcome::Error, &local(&err))),
+        }
+    }
+}
+
+fn describe(message: &ServerMessage) -> String {
+    match message {
+        ServerMessage::Welcome { min, max } => tr(
+            "secret_between",
+            &[("min", &num(*min)), ("max", &num(*max))],
+        ),
+        ServerMessage::Joined(name) => tr("player_joined", &[("name", name)]),
+        ServerMessage::Result {
+            guess,
+            outcome,
+            name,
+        } => {
+            let hint = match outcome {
+                GuessOutcome::TooBig => tr("too_big", &[]),
+                _ => tr("too_small", &[]),
+            };
+            let hint = paint((*outcome).into(), &hint);
+            tr(
+                "player_guessed",
+                &[("name", name), ("guess", &num(*guess)), ("hint", &hint)],
+            )
+        }
+        ServerMessage::Win {
+            guess,
+            attempts,
+            name,
+        } => format!(
+            "{} {}",
+            paint(Outcome::Win, &tr("winner_announced", &[])),
+            tr(
+                "player_found_in",
+                &[
+                    ("name", name),
+                    ("guess", &num(*guess)),
+                    ("attempts", attempts),
+                ]
+            )
+        ),
+        ServerMessage::Error(message) => paint(Outcome::Error, message).to_string(),
+    }
+}
+
+/// Adds the finished game to the leaderboard. A broken or missing data
+/// directory should not spoil the game, so failures are only reported.
+fn save_result(player: &str, difficulty: &str, game: &Game) {
+    let Some(path) = Leaderboard::default_path() else {
+        eprintln!("{}", warning(&tr("no_data_dir", &[])));
+        return;
+    };
+    let won = game.status() == Status::Won;
+    if let Err(err) = Leaderboard::record_game(&path, player, difficulty, won, game.attempts()) {
+        eprintln!("{}", warning(&result_not_saved(&path, &err)));
+        return;
+    }
+    award_achievements(&path, player, difficulty, game);
+}
+
+fn result_not_saved(path: &Path, err: &io::Error) -> String {
+    tr(
+        "result_not_saved",
+        &[("path", &path.display()), ("error", err)],
+    )
+}
+
+/// Unlocks the achievements a recorded game earned and announces them.
+fn award_achievements(path: &Path, player: &str, difficulty: &str, game: &Game) {
+    match Leaderboard::award(path, player, difficulty, game) {
+        Ok(unlocked) => {
+            for achievement in unlocked {
+                println!(
+                    "{}",
+                    tr(
+                        "unlocked",
+                        &[
+                            ("player", &player),
+                            ("name", &(achievement.name)(lang()).bold()),
+                            ("description", &(achievement.description)(lang())),
+                        ]
+                    )
+                );
+            }
+        }
+        Err(err) => eprintln!(
+            "{}",
+            warning(&tr(
+                "achievements_not_saved",
+                &[("path", &path.display()), ("error", &err)]
+            ))
+        ),
+    }
+}
+
+/// Rates a finished solo game against par and shows the new rating.
+fn save_rating(player: &str, game: &Game) {
+    let Some(path) = Ratings::default_path() else {
+        return;
+    };
+    match Ratings::record_solo_game(&path, player, game) {
+        Ok(entry) => println!(
+            "{}",
+            tr(
+                "rating",
+                &[
+                    ("rating", &format!("{:.0}", entry.rating)),
+                    ("change", &format!("{:+}", entry.change.round() as i64)),
+                ]
+            )
+        ),
+        Err(err) => eprintln!(
+            "{}",
+            warning(&tr(
+                "rating_not_saved",
+                &[("path", &path.display()), ("error", &err)]
+            ))
+        ),
+    }
+}
+
+/// Rates a hot-seat or network match and shows everyone's new rating.
+/// `places` gives each player's finishing place, 1 for the winner.
+fn save_match(kind: MatchKind, places: &[(String, u32)]) {
+    let Some(path) = Ratings::default_path() else {
+        return;
+    };
+    match Ratings::record_match_game(&path, kind, places) {
+        Ok(entries) => {
+            for ((player, _), entry) in places.iter().zip(&entries) {
+                println!(
+                    "{}",
+                    tr(
+                        "match_rating",
+                        &[
+                            ("player", &format!("{:<16}", player)),
+                            ("rating", &format!("{:.0}", entry.rating)),
+                            ("change", &format!("{:+}", entry.change.round() as i64)),
+                        ]
+                    )
+                );
+            }
+        }
+        Err(err) => eprintln!(
+            "{}",
+            warning(&tr(
+                "ratings_not_saved",
+                &[("path", &path.display()), ("error", &err)]
+            ))
+        ),
+    }
+}
+
+fn run_bench(options: &Options, games: usize) {
+    let seed = options.seed.unwrap_or(0);
+    let mut strategies = if options.strategies.is_empty() {
+        strategy::all(seed)
+    } else {
+        options
+            .strategies
+            .iter()
+            .filter_map(|name| strategy::by_name(name, seed))
+            .collect()
+    };
+
+    let mut reports = Vec::new();
+    for strategy in &mut strategies {
+        match bench::run(strategy.as_mut(), games, seed, options.range()) {
+            Ok(report) => reports.push(report),
+            // Too slow for this range, which is worth knowing but should
+            // not hide the other strategies' results.
+            Err(err @ BenchError::GaveUp { .. }) => eprintln!(
+                "{}",
+                warning(&tr(
+                    "gave_up",
+                    &[("strategy", &strategy.name()), ("error", &local(&err))]
+                ))
+            ),
+            Err(err) => {
+                let message = tr(
+                    "strategy_failed",
+                    &[("strategy", &strategy.name()), ("error", &local(&err))],
+                );
+                eprintln!("{}", error(&message));
+                process::exit(1);
+            }
+        }
+    }
+
+    if options.csv {
+        print!("{}", bench::csv(&reports));
+    } else {
+        print!("{}", bench::table(&reports, lang()));
+    }
+}
+
+/// Prints every rated player's current rating, peak and games, followed
+/// by a chart of each history. Only `player` is shown if given.
+fn print_ratings(player: Option<&str>) {
+    let ratings = Ratings::default_path()
+        .map(|path| Ratings::load(&path))
+        .unwrap_or_else(|| Ok(Ratings::default()));
+    let ratings = match ratings {
+        Ok(ratings) => ratings,
+        Err(err) => {
+            eprintln!("{}", error(&tr("ratings_unreadable", &[("error", &err)])));
+            process::exit(EXIT_IO);
+        }
+    };
+    let players: Vec<&String> = ratings
+        .players
+        .keys()
+        .filter(|name| player.is_none_or(|player| *name == player))
+        .collect();
+    if players.is_empty() {
+        match player {
+            Some(player) => println!("{}", tr("no_rated_games_for", &[("player", &player)])),
+            None => println!("{}", tr("no_rated_games", &[])),
+        }
+        return;
+    }
+
+    println!(
+        "{:<16} {:>6} {:>6} {:>6}",
+        tr("column_player", &[]),
+        tr("column_rating", &[]),
+        tr("column_peak", &[]),
+        tr("column_games", &[])
+    );
+    for name in &players {
+        let history = ratings.history(name);
+        let peak = history
+            .iter()
+            .map(|entry| entry.rating)
+            .fold(rating::INITIAL, f64::max);
+        println!(
+            "{:<16} {:>6.0} {:>6.0} {:>6}",
+            name,
+            ratings.rating(name),
+            peak,
+            history.len()
+        );
+    }
+    for name in players {
+        println!();
+        println!("{}", name.bold());
+        print!("{}", rating::chart(ratings.history(name), 10));
+    }
+}
+
+fn print_stats() {
+    let board = Leaderboard::default_path()
+        .map(|path| Leaderboard::load(&path))
+        .unwrap_or_else(|| Ok(Leaderboard::default()));
+    match board {
+        Ok(board) => print!("{}", local(&board)),
+        Err(err) => {
+            eprintln!(
+                "{}",
+                error(&tr("leaderboard_unreadable", &[("error", &err)]))
+            );
+            process::exit(EXIT_IO);
         }
     }
 }
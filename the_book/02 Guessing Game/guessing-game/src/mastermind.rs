@@ -0,0 +1,546 @@
+//! Bulls and cows: the secret is a code of digits instead of a number, and
+//! each guess is scored by how many digits are in the right place (bulls)
+//! and how many are right but misplaced (cows).
+
+use crate::difficulty::Difficulty;
+use crate::game::{GuessError, Status};
+use crate::i18n::{Lang, Localize};
+use rand::distributions::Uniform;
+use rand::prng::ChaChaRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+/// The shape of the codes in a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// How many digits a code has.
+    pub length: usize,
+    /// Codes use the digits `0` up to but not including `digits`.
+    pub digits: u8,
+    /// Whether a digit may appear more than once.
+    pub repeats: bool,
+}
+
+impl Rules {
+    /// The longest code a game can use.
+    pub const MAX_LENGTH: usize = 6;
+
+    /// Easy is a short Mastermind, Normal the classic 4 pegs in 6 colors,
+    /// Hard the classic bulls and cows and Insane a longer version of it.
+    pub fn preset(difficulty: Difficulty) -> Rules {
+        let (length, digits, repeats) = match difficulty {
+            Difficulty::Easy => (3, 6, true),
+            Difficulty::Normal => (4, 6, true),
+            Difficulty::Hard => (4, 10, false),
+            Difficulty::Insane => (5, 10, false),
+        };
+        Rules {
+            length,
+            digits,
+            repeats,
+        }
+    }
+
+    /// The attempt budget for a preset.
+    pub fn attempts(difficulty: Difficulty) -> u32 {
+        match difficulty {
+            Difficulty::Easy => 8,
+            Difficulty::Normal | Difficulty::Hard => 10,
+            Difficulty::Insane => 12,
+        }
+    }
+
+    /// Whether any code fits these rules.
+    pub fn is_possible(&self) -> bool {
+        (1..=Rules::MAX_LENGTH).contains(&self.length)
+            && (1..=10).contains(&self.digits)
+            && (self.repeats || self.length <= usize::from(self.digits))
+    }
+
+    /// Every code that fits the rules, in increasing order.
+    pub fn codes(&self) -> Vec<Code> {
+        let mut codes: Vec<Vec<u8>> = vec![Vec::new()];
+        for _ in 0..self.length {
+            let mut longer = Vec::new();
+            for code in &codes {
+                for digit in 0..self.digits {
+                    if self.repeats || !code.contains(&digit) {
+                        let mut next = code.clone();
+                        next.push(digit);
+                        longer.push(next);
+                    }
+                }
+            }
+            codes = longer;
+        }
+        codes.into_iter().map(Code).collect()
+    }
+
+    /// Draws a code uniformly from the ones that fit the rules.
+    pub fn random_code<R: Rng + ?Sized>(&self, rng: &mut R) -> Code {
+        if self.repeats {
+            let digit = Uniform::new(0, self.digits);
+            Code((0..self.length).map(|_| rng.sample(digit)).collect())
+        } else {
+            let mut digits: Vec<u8> = (0..self.digits).collect();
+            rng.shuffle(&mut digits);
+            digits.truncate(self.length);
+            Code(digits)
+        }
+    }
+}
+
+impl Localize for Rules {
+    fn localize(&self, lang: Lang) -> String {
+        let args: &[(&str, &dyn fmt::Display)] =
+            &[("length", &self.length), ("highest", &(self.digits - 1))];
+        if self.repeats {
+            lang.format("code_rules_repeats", args)
+        } else {
+            lang.format("code_rules_unique", args)
+        }
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+/// A secret code or a guess at one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Code(Vec<u8>);
+
+impl Code {
+    pub fn digits(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|digit| write!(f, "{}", digit))
+    }
+}
+
+/// Why a line of input is not a valid code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCodeError {
+    Empty,
+    WrongLength { expected: usize, got: usize },
+    InvalidDigit(char),
+    Repeated(u8),
+}
+
+impl Localize for ParseCodeError {
+    fn localize(&self, lang: Lang) -> String {
+        match self {
+            ParseCodeError::Empty => lang.format("empty_code", &[]),
+            ParseCodeError::WrongLength { expected, got } => {
+                lang.format("code_length", &[("expected", expected), ("got", got)])
+            }
+            ParseCodeError::InvalidDigit(c) => lang.format("not_a_digit", &[("digit", c)]),
+            ParseCodeError::Repeated(digit) => lang.format("repeated_digit", &[("digit", digit)]),
+        }
+    }
+}
+
+impl fmt::Display for ParseCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for ParseCodeError {}
+
+/// Parses one line typed by the player. Spaces between digits are allowed.
+pub fn parse_code(line: &str, rules: &Rules) -> Result<Code, ParseCodeError> {
+    let mut digits = Vec::new();
+    for c in line.chars().filter(|c| !c.is_whitespace()) {
+        match c.to_digit(10) {
+            Some(digit) if digit < u32::from(rules.digits) => {
+                let digit = digit as u8;
+                if !rules.repeats && digits.contains(&digit) {
+                    return Err(ParseCodeError::Repeated(digit));
+                }
+                digits.push(digit);
+            }
+            _ => return Err(ParseCodeError::InvalidDigit(c)),
+        }
+    }
+    match digits.len() {
+        0 => Err(ParseCodeError::Empty),
+        got if got != rules.length => Err(ParseCodeError::WrongLength {
+            expected: rules.length,
+            got,
+        }),
+        _ => Ok(Code(digits)),
+    }
+}
+
+/// The feedback for a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Score {
+    pub bulls: usize,
+    pub cows: usize,
+}
+
+impl Localize for Score {
+    fn localize(&self, lang: Lang) -> String {
+        let bulls = match self.bulls {
+            1 => lang.format("one_bull", &[]),
+            count => lang.format("bulls", &[("count", &count)]),
+        };
+        let cows = match self.cows {
+            1 => lang.format("one_cow", &[]),
+            count => lang.format("cows", &[("count", &count)]),
+        };
+        lang.format("bulls_cows", &[("bulls", &bulls), ("cows", &cows)])
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+/// Scores `guess` against `secret`.
+pub fn score(secret: &Code, guess: &Code) -> Score {
+    let bulls = secret
+        .0
+        .iter()
+        .zip(&guess.0)
+        .filter(|(a, b)| a == b)
+        .count();
+    let mut counts = [0usize; 10];
+    for &digit in &secret.0 {
+        counts[usize::from(digit)] += 1;
+    }
+    let mut common = 0;
+    for &digit in &guess.0 {
+        if counts[usize::from(digit)] > 0 {
+            counts[usize::from(digit)] -= 1;
+            common += 1;
+        }
+    }
+    Score {
+        bulls,
+        cows: common - bulls,
+    }
+}
+
+/// What one line of player input did to a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeTurn {
+    Invalid(ParseCodeError),
+    Rejected(GuessError),
+    Scored { guess: Code, score: Score },
+}
+
+/// A game of bulls and cows.
+#[derive(Debug, Clone)]
+pub struct CodeGame {
+    secret: Code,
+    rules: Rules,
+    max_attempts: Option<u32>,
+    history: Vec<(Code, Score)>,
+}
+
+impl CodeGame {
+    /// Starts a game with a known secret.
+    pub fn new(secret: Code, rules: Rules) -> CodeGame {
+        CodeGame {
+            secret,
+            rules,
+            max_attempts: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// Starts a game whose secret is fully determined by `seed`.
+    pub fn seeded(seed: u64, rules: Rules) -> CodeGame {
+        let secret = rules.random_code(&mut ChaChaRng::seed_from_u64(seed));
+        CodeGame::new(secret, rules)
+    }
+
+    /// Limits the game to `max_attempts` guesses, after which it is lost.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> CodeGame {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    pub fn guess(&mut self, guess: Code) -> Result<Score, GuessError> {
+        if self.status() != Status::Playing {
+            return Err(GuessError::GameOver);
+        }
+        let score = score(&self.secret, &guess);
+        self.history.push((guess, score));
+        Ok(score)
+    }
+
+    /// Parses a raw line of input and, if it holds a code, guesses it.
+    pub fn take_turn(&mut self, line: &str) -> CodeTurn {
+        let guess = match parse_code(line, &self.rules) {
+            Ok(guess) => guess,
+            Err(err) => return CodeTurn::Invalid(err),
+        };
+        match self.guess(guess.clone()) {
+            Ok(score) => CodeTurn::Scored { guess, score },
+            Err(err) => CodeTurn::Rejected(err),
+        }
+    }
+
+    pub fn secret(&self) -> &Code {
+        &self.secret
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn history(&self) -> &[(Code, Score)] {
+        &self.history
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn status(&self) -> Status {
+        match self.history.last() {
+            Some((_, score)) if score.bulls == self.rules.length => Status::Won,
+            _ if self.attempts_left() == Some(0) => Status::Lost,
+            _ => Status::Playing,
+        }
+    }
+}
+
+/// Picks guesses the way Knuth's Mastermind algorithm does: the guess whose
+/// worst possible score leaves the fewest codes, preferring codes that could
+/// still be the secret. For large games only a sample of the candidates is
+/// considered as guesses so each move stays quick.
+#[derive(Debug, Clone)]
+pub struct Solver {
+    rules: Rules,
+    all: Vec<Code>,
+    candidates: Vec<Code>,
+}
+
+impl Solver {
+    /// Above this many guess/candidate pairs the guesses are drawn from the
+    /// candidates only.
+    const FULL_SEARCH: usize = 1_000_000;
+    /// The most guesses weighed in one move.
+    const SAMPLE: usize = 500;
+
+    pub fn new(rules: &Rules) -> Solver {
+        let all = rules.codes();
+        Solver {
+            rules: *rules,
+            candidates: all.clone(),
+            all,
+        }
+    }
+
+    /// Every code that agrees with the scores so far.
+    pub fn candidates(&self) -> &[Code] {
+        &self.candidates
+    }
+
+    pub fn next_guess(&self) -> Code {
+        if self.candidates.len() <= 2 {
+            return self.candidates[0].clone();
+        }
+        if self.candidates.len() == self.all.len() {
+            return self.opening();
+        }
+
+        let pool: Vec<&Code> = if self.all.len() * self.candidates.len() <= Solver::FULL_SEARCH {
+            self.candidates.iter().chain(&self.all).collect()
+        } else {
+            let step = self.candidates.len().div_ceil(Solver::SAMPLE);
+            self.candidates.iter().step_by(step).collect()
+        };
+
+        let mut best = (usize::MAX, pool[0]);
+        for guess in pool {
+            let worst = self.worst_case(guess);
+            if worst < best.0 {
+                best = (worst, guess);
+            }
+        }
+        best.1.clone()
+    }
+
+    /// Knuth's opening, two pairs of digits, generalised to any length. With
+    /// no repeats it is simply the first code.
+    fn opening(&self) -> Code {
+        if !self.rules.repeats {
+            return self.all[0].clone();
+        }
+        Code((0..self.rules.length).map(|i| (i / 2) as u8).collect())
+    }
+
+    /// How many candidates the worst score for `guess` would leave.
+    fn worst_case(&self, guess: &Code) -> usize {
+        let mut partitions = [0; (Rules::MAX_LENGTH + 1) * (Rules::MAX_LENGTH + 1)];
+        for candidate in &self.candidates {
+            let score = score(candidate, guess);
+            partitions[score.bulls * (Rules::MAX_LENGTH + 1) + score.cows] += 1;
+        }
+        partitions.into_iter().max().unwrap_or(0)
+    }
+
+    /// Narrows the candidates down to the codes that would have scored
+    /// `guess` the same way.
+    pub fn answer(&mut self, guess: &Code, result: Score) {
+        self.candidates
+            .retain(|candidate| score(candidate, guess) == result);
+    }
+}
+
+/// Lets `Solver` crack `secret` and returns its guesses, stopping after
+/// `max_attempts`.
+pub fn solve(secret: &Code, rules: &Rules, max_attempts: u32) -> Vec<(Code, Score)> {
+    let mut game = CodeGame::new(secret.clone(), *rules).with_max_attempts(max_attempts);
+    let mut solver = Solver::new(rules);
+    while game.status() == Status::Playing {
+        let guess = solver.next_guess();
+        let result = game.guess(guess.clone()).expect("the game is still going");
+        solver.answer(&guess, result);
+    }
+    game.history().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(digits: &str) -> Code {
+        Code(digits.bytes().map(|b| b - b'0').collect())
+    }
+
+    #[test]
+    fn scores_count_bulls_then_cows() {
+        assert_eq!(
+            score(&code("1234"), &code("1234")),
+            Score { bulls: 4, cows: 0 }
+        );
+        assert_eq!(
+            score(&code("1234"), &code("4321")),
+            Score { bulls: 0, cows: 4 }
+        );
+        assert_eq!(
+            score(&code("1122"), &code("1213")),
+            Score { bulls: 1, cows: 2 }
+        );
+        assert_eq!(
+            score(&code("0000"), &code("0011")),
+            Score { bulls: 2, cows: 0 }
+        );
+        assert_eq!(Score { bulls: 1, cows: 2 }.to_string(), "1 bull, 2 cows");
+    }
+
+    #[test]
+    fn codes_are_parsed_against_the_rules() {
+        let rules = Rules::preset(Difficulty::Hard);
+        assert_eq!(parse_code(" 12 34\n", &rules), Ok(code("1234")));
+        assert_eq!(parse_code("\n", &rules), Err(ParseCodeError::Empty));
+        assert_eq!(
+            parse_code("123", &rules),
+            Err(ParseCodeError::WrongLength {
+                expected: 4,
+                got: 3
+            })
+        );
+        assert_eq!(parse_code("1231", &rules), Err(ParseCodeError::Repeated(1)));
+        assert_eq!(
+            parse_code("12a4", &rules),
+            Err(ParseCodeError::InvalidDigit('a'))
+        );
+
+        let rules = Rules::preset(Difficulty::Normal);
+        assert_eq!(
+            parse_code("1126", &rules),
+            Err(ParseCodeError::InvalidDigit('6'))
+        );
+        assert_eq!(parse_code("1155", &rules), Ok(code("1155")));
+    }
+
+    #[test]
+    fn presets_fit_their_rules() {
+        for difficulty in Difficulty::ALL {
+            let rules = Rules::preset(difficulty);
+            assert!(rules.is_possible());
+            let secret = CodeGame::seeded(1, rules).secret().clone();
+            assert_eq!(parse_code(&secret.to_string(), &rules), Ok(secret));
+        }
+        assert_eq!(Rules::preset(Difficulty::Normal).codes().len(), 1296);
+        assert_eq!(Rules::preset(Difficulty::Hard).codes().len(), 5040);
+        assert!(
+            !Rules {
+                length: 5,
+                digits: 4,
+                repeats: false
+            }
+            .is_possible()
+        );
+    }
+
+    #[test]
+    fn games_are_won_with_every_bull() {
+        let mut game =
+            CodeGame::new(code("123"), Rules::preset(Difficulty::Easy)).with_max_attempts(2);
+        assert!(matches!(game.take_turn("9"), CodeTurn::Invalid(_)));
+        assert_eq!(
+            game.take_turn("321"),
+            CodeTurn::Scored {
+                guess: code("321"),
+                score: Score { bulls: 1, cows: 2 }
+            }
+        );
+        assert_eq!(game.status(), Status::Playing);
+        game.take_turn("123");
+        assert_eq!(game.status(), Status::Won);
+        assert_eq!(
+            game.take_turn("123"),
+            CodeTurn::Rejected(GuessError::GameOver)
+        );
+    }
+
+    #[test]
+    fn the_solver_cracks_every_easy_code_in_budget() {
+        let rules = Rules::preset(Difficulty::Easy);
+        for secret in rules.codes() {
+            let history = solve(&secret, &rules, Rules::attempts(Difficulty::Easy));
+            assert_eq!(history.last().unwrap().0, secret);
+        }
+    }
+
+    #[test]
+    fn the_solver_cracks_mastermind_in_five() {
+        let rules = Rules::preset(Difficulty::Normal);
+        for secret in rules.codes().into_iter().step_by(97) {
+            let history = solve(&secret, &rules, 10);
+            assert_eq!(history.last().unwrap().0, secret);
+            assert!(history.len() <= 5, "{} took {}", secret, history.len());
+        }
+    }
+
+    #[test]
+    fn the_solver_handles_bulls_and_cows() {
+        let rules = Rules::preset(Difficulty::Hard);
+        for seed in 0..5 {
+            let secret = CodeGame::seeded(seed, rules).secret().clone();
+            let history = solve(&secret, &rules, Rules::attempts(Difficulty::Hard));
+            assert_eq!(history.last().unwrap().0, secret);
+        }
+    }
+}
@@ -0,0 +1,442 @@
+//! Multiplayer over TCP: players race to guess one shared secret.
+//!
+//! # Protocol
+//!
+//! Messages are single lines of UTF-8 text ending in `\n`. A client sends:
+//!
+//! * `JOIN <name>` — must be the first message; names may contain spaces.
+//! * `GUESS <n>` — a guess at the shared secret.
+//!
+//! The server sends:
+//!
+//! * `WELCOME <min> <max>` — to a client once it has joined.
+//! * `JOIN <name>` — to everyone else when a player joins.
+//! * `RESULT <guess> TOO_BIG|TOO_SMALL <name>` — to everyone after a wrong guess.
+//! * `WIN <guess> <attempts> <name>` — to everyone when the secret is found,
+//!   after which the server closes every connection.
+//! * `ERROR <message>` — to a client whose message was rejected.
+
+use crate::{Game, GuessError, GuessOutcome};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientMessage {
+    Join(String),
+    Guess(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerMessage {
+    Welcome {
+        min: u32,
+        max: u32,
+    },
+    Joined(String),
+    Result {
+        guess: u32,
+        outcome: GuessOutcome,
+        name: String,
+    },
+    Win {
+        guess: u32,
+        attempts: u32,
+        name: String,
+    },
+    Error(String),
+}
+
+/// A line that is not a valid protocol message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMessageError(String);
+
+impl fmt::Display for ParseMessageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "malformed message `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseMessageError {}
+
+impl fmt::Display for ClientMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientMessage::Join(name) => write!(f, "JOIN {}", name),
+            ClientMessage::Guess(guess) => write!(f, "GUESS {}", guess),
+        }
+    }
+}
+
+impl FromStr for ClientMessage {
+    type Err = ParseMessageError;
+
+    fn from_str(line: &str) -> Result<ClientMessage, ParseMessageError> {
+        let line = line.trim_end();
+        let err = || ParseMessageError(line.to_string());
+        match line.split_once(' ').ok_or_else(err)? {
+            ("JOIN", name) if !name.trim().is_empty() => {
+                Ok(ClientMessage::Join(name.trim().to_string()))
+            }
+            ("GUESS", guess) => guess.parse().map(ClientMessage::Guess).map_err(|_| err()),
+            _ => Err(err()),
+        }
+    }
+}
+
+impl fmt::Display for ServerMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServerMessage::Welcome { min, max } => write!(f, "WELCOME {} {}", min, max),
+            ServerMessage::Joined(name) => write!(f, "JOIN {}", name),
+            ServerMessage::Result {
+                guess,
+                outcome,
+                name,
+            } => {
+                let outcome = match outcome {
+                    GuessOutcome::TooBig => "TOO_BIG",
+                    GuessOutcome::TooSmall => "TOO_SMALL",
+                    GuessOutcome::Win => "WIN",
+                };
+                write!(f, "RESULT {} {} {}", guess, outcome, name)
+            }
+            ServerMessage::Win {
+                guess,
+                attempts,
+                name,
+            } => write!(f, "WIN {} {} {}", guess, attempts, name),
+            ServerMessage::Error(message) => write!(f, "ERROR {}", message),
+        }
+    }
+}
+
+impl FromStr for ServerMessage {
+    type Err = ParseMessageError;
+
+    fn from_str(line: &str) -> Result<ServerMessage, ParseMessageError> {
+        let line = line.trim_end();
+        let err = || ParseMessageError(line.to_string());
+        let mut parts = line.splitn(4, ' ');
+        let mut next = || parts.next().ok_or_else(err);
+        let message = match next()? {
+            "WELCOME" => ServerMessage::Welcome {
+                min: next()?.parse().map_err(|_| err())?,
+                max: next()?.parse().map_err(|_| err())?,
+            },
+            "JOIN" => ServerMessage::Joined(rest(line, "JOIN").ok_or_else(err)?),
+            "RESULT" => {
+                let guess = next()?.parse().map_err(|_| err())?;
+                let outcome = match next()? {
+                    "TOO_BIG" => GuessOutcome::TooBig,
+                    "TOO_SMALL" => GuessOutcome::TooSmall,
+                    _ => return Err(err()),
+                };
+                ServerMessage::Result {
+                    guess,
+                    outcome,
+                    name: next()?.to_string(),
+                }
+            }
+            "WIN" => ServerMessage::Win {
+                guess: next()?.parse().map_err(|_| err())?,
+                attempts: next()?.parse().map_err(|_| err())?,
+                name: next()?.to_string(),
+            },
+            "ERROR" => ServerMessage::Error(rest(line, "ERROR").unwrap_or_default()),
+            _ => return Err(err()),
+        };
+        Ok(message)
+    }
+}
+
+/// Everything after `keyword` and the space that follows it.
+fn rest(line: &str, keyword: &str) -> Option<String> {
+    line.strip_prefix(keyword)?
+        .strip_prefix(' ')
+        .map(str::to_string)
+}
+
+/// The player who found the secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Winner {
+    pub name: String,
+    pub secret: u32,
+    pub attempts: u32,
+    /// Everyone else still playing when the secret was found, by name.
+    pub others: Vec<String>,
+}
+
+/// Everything the connection threads share.
+struct State {
+    game: Game,
+    /// Write halves of every joined client, by connection id.
+    clients: HashMap<usize, (String, TcpStream)>,
+    attempts: HashMap<usize, u32>,
+}
+
+impl State {
+    fn broadcast(&mut self, message: &ServerMessage) {
+        // A client that has gone away is dropped on its next failed write.
+        self.clients
+            .retain(|_, (_, stream)| writeln!(stream, "{}", message).is_ok());
+    }
+}
+
+/// Hosts one round of the game and reports the winner.
+pub struct Server {
+    listener: TcpListener,
+    game: Game,
+}
+
+impl Server {
+    pub fn bind<A: ToSocketAddrs>(addr: A, game: Game) -> io::Result<Server> {
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            game,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts players until one of them guesses the secret.
+    ///
+    /// `on_event` sees every message the server broadcasts, so the host
+    /// can follow the game.
+    pub fn run<F>(self, mut on_event: F) -> io::Result<Winner>
+    where
+        F: FnMut(&ServerMessage),
+    {
+        let state = Arc::new(Mutex::new(State {
+            game: self.game,
+            clients: HashMap::new(),
+            attempts: HashMap::new(),
+        }));
+        let (events, received) = mpsc::channel();
+
+        let listener = self.listener.try_clone()?;
+        let accept_state = Arc::clone(&state);
+        thread::spawn(move || {
+            for (id, stream) in listener.incoming().enumerate() {
+                let Ok(stream) = stream else { continue };
+                let state = Arc::clone(&accept_state);
+                let events = events.clone();
+                thread::spawn(move || {
+                    let _ = serve_client(id, stream, &state, &events);
+                    let mut state = state.lock().unwrap();
+                    state.clients.remove(&id);
+                });
+            }
+        });
+
+        for event in received {
+            on_event(&event);
+            if let ServerMessage::Win {
+                guess,
+                attempts,
+                name,
+            } = event
+            {
+                let mut state = state.lock().unwrap();
+                let mut others: Vec<String> = state
+                    .clients
+                    .values()
+                    .map(|(player, _)| player.clone())
+                    .filter(|player| *player != name)
+                    .collect();
+                others.sort();
+                for (_, stream) in state.clients.values() {
+                    let _ = stream.shutdown(Shutdown::Both);
+                }
+                state.clients.clear();
+                return Ok(Winner {
+                    name,
+                    secret: guess,
+                    attempts,
+                    others,
+                });
+            }
+        }
+        Err(io::Error::other("the server stopped accepting players"))
+    }
+}
+
+fn serve_client(
+    id: usize,
+    stream: TcpStream,
+    state: &Mutex<State>,
+    events: &Sender<ServerMessage>,
+) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut name = None;
+
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        let reply = match (line.parse::<ClientMessage>(), &name) {
+            (Err(err), _) => Some(ServerMessage::Error(err.to_string())),
+            (Ok(ClientMessage::Join(_)), Some(_)) => {
+                Some(ServerMessage::Error("already joined".to_string()))
+            }
+            (Ok(ClientMessage::Guess(_)), None) => {
+                Some(ServerMessage::Error("send JOIN <name> first".to_string()))
+            }
+            (Ok(ClientMessage::Join(joined)), None) => {
+                let mut state = state.lock().unwrap();
+                // Players are told apart by name, in messages and results.
+                if state.clients.values().any(|(player, _)| *player == joined) {
+                    Some(ServerMessage::Error(format!(
+                        "the name {} is already taken",
+                        joined
+                    )))
+                } else {
+                    let range = state.game.range().clone();
+                    let announcement = ServerMessage::Joined(joined.clone());
+                    state.broadcast(&announcement);
+                    let _ = events.send(announcement);
+                    writeln!(
+                        writer,
+                        "{}",
+                        ServerMessage::Welcome {
+                            min: *range.start(),
+                            max: *range.end(),
+                        }
+                    )?;
+                    state
+                        .clients
+                        .insert(id, (joined.clone(), writer.try_clone()?));
+                    state.attempts.insert(id, 0);
+                    name = Some(joined);
+                    None
+                }
+            }
+            (Ok(ClientMessage::Guess(guess)), Some(name)) => {
+                let mut state = state.lock().unwrap();
+                match state.game.guess(guess) {
+                    Ok(outcome) => {
+                        let attempts = state.attempts.entry(id).or_insert(0);
+                        *attempts += 1;
+                        let message = match outcome {
+                            GuessOutcome::Win => ServerMessage::Win {
+                                guess,
+                                attempts: *attempts,
+                                name: name.clone(),
+                            },
+                            _ => ServerMessage::Result {
+                                guess,
+                                outcome,
+                                name: name.clone(),
+                            },
+                        };
+                        state.broadcast(&message);
+                        let _ = events.send(message);
+                        None
+                    }
+                    Err(GuessError::GameOver) => {
+                        Some(ServerMessage::Error("the game is over".to_string()))
+                    }
+                    Err(err) => Some(ServerMessage::Error(err.to_string())),
+                }
+            }
+        };
+        if let Some(reply) = reply {
+            writeln!(writer, "{}", reply)?;
+        }
+    }
+    Ok(())
+}
+
+/// A player's connection to a [`Server`].
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    /// Connects and joins as `name`, returning the range of the secret.
+    pub fn join<A: ToSocketAddrs>(addr: A, name: &str) -> io::Result<(Client, (u32, u32))> {
+        let writer = TcpStream::connect(addr)?;
+        let mut client = Client {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+        };
+        client.send(&ClientMessage::Join(name.to_string()))?;
+        match client.receive()? {
+            Some(ServerMessage::Welcome { min, max }) => Ok((client, (min, max))),
+            Some(ServerMessage::Error(message)) => Err(io::Error::other(message)),
+            other => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected WELCOME, got {:?}", other),
+            )),
+        }
+    }
+
+    pub fn send(&mut self, message: &ClientMessage) -> io::Result<()> {
+        writeln!(self.writer, "{}", message)
+    }
+
+    /// The next message from the server, or `None` once it hangs up.
+    pub fn receive(&mut self) -> io::Result<Option<ServerMessage>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        line.parse()
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// A second handle for sending, so one thread can read while another
+    /// writes.
+    pub fn sender(&self) -> io::Result<TcpStream> {
+        self.writer.try_clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_messages_round_trip() {
+        for message in [
+            ClientMessage::Join("Ana Lee".into()),
+            ClientMessage::Guess(42),
+        ] {
+            assert_eq!(message.to_string().parse(), Ok(message));
+        }
+        assert!("GUESS x".parse::<ClientMessage>().is_err());
+        assert!("JOIN ".parse::<ClientMessage>().is_err());
+        assert!("HELLO".parse::<ClientMessage>().is_err());
+    }
+
+    #[test]
+    fn server_messages_round_trip() {
+        for message in [
+            ServerMessage::Welcome { min: 1, max: 100 },
+            ServerMessage::Joined("Ana Lee".into()),
+            ServerMessage::Result {
+                guess: 50,
+                outcome: GuessOutcome::TooBig,
+                name: "Ana Lee".into(),
+            },
+            ServerMessage::Win {
+                guess: 42,
+                attempts: 3,
+                name: "bo".into(),
+            },
+            ServerMessage::Error("send JOIN <name> first".into()),
+        ] {
+            assert_eq!(message.to_string().parse(), Ok(message));
+        }
+        assert!("RESULT 5 SIDEWAYS bo".parse::<ServerMessage>().is_err());
+        assert!("JOIN".parse::<ServerMessage>().is_err());
+        assert_eq!("ERROR".parse(), Ok(ServerMessage::Error(String::new())));
+    }
+}
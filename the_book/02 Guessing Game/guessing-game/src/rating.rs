@@ -0,0 +1,281 @@
+//! Elo-style skill ratings. A solo game is a match against par, an
+//! imaginary player who always needs exactly the optimal number of attempts;
+//! hot-seat and network rounds are matches between the players themselves.
+
+use crate::daily::Date;
+use crate::game::{Game, Status, optimal_attempts};
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Every player's rating before their first game, and par's rating.
+pub const INITIAL: f64 = 1200.0;
+
+/// The most a rating can move in one game.
+const K: f64 = 32.0;
+
+/// How many of the latest games [`chart`] draws.
+pub const CHART_WIDTH: usize = 60;
+
+/// The chance of beating `opponent`, as Elo predicts it from the ratings.
+pub fn expected(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+/// How a solo game went against par: a draw (0.5) for a win in exactly the
+/// optimal number of attempts, better for fewer and worse for more, down to
+/// a loss (0.0) at twice as many or when the secret was never found.
+pub fn solo_score(game: &Game) -> f64 {
+    if game.status() != Status::Won {
+        return 0.0;
+    }
+    let optimal = f64::from(optimal_attempts(game.range()));
+    let attempts = f64::from(game.attempts());
+    (0.5 + (optimal - attempts) / (2.0 * optimal)).clamp(0.0, 1.0)
+}
+
+/// What kind of game a rating change came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    Solo,
+    HotSeat,
+    Network,
+}
+
+/// A player's rating after one game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub rating: f64,
+    pub change: f64,
+    pub kind: MatchKind,
+    pub date: String,
+}
+
+/// Every player's rating history, oldest first, keyed by player name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ratings {
+    pub players: BTreeMap<String, Vec<Entry>>,
+}
+
+impl Ratings {
+    /// Where ratings live unless a caller picks another file.
+    pub fn default_path() -> Option<PathBuf> {
+        storage::data_dir().map(|dir| dir.join("ratings.json"))
+    }
+
+    pub fn load(path: &Path) -> io::Result<Ratings> {
+        storage::load_json(path)
+    }
+
+    /// Rates a solo game in the file at `path` and returns the new entry.
+    pub fn record_solo_game(path: &Path, player: &str, game: &Game) -> io::Result<Entry> {
+        let mut entry = None;
+        storage::update_json(path, |ratings: &mut Ratings| {
+            entry = Some(ratings.record_solo(player, game, &Date::today().to_string()));
+        })?;
+        Ok(entry.expect("the update always runs"))
+    }
+
+    /// Rates a match in the file at `path` and returns each player's new
+    /// entry, in the order of `places`.
+    pub fn record_match_game(
+        path: &Path,
+        kind: MatchKind,
+        places: &[(String, u32)],
+    ) -> io::Result<Vec<Entry>> {
+        let mut entries = Vec::new();
+        storage::update_json(path, |ratings: &mut Ratings| {
+            entries = ratings.record_match(kind, places, &Date::today().to_string());
+        })?;
+        Ok(entries)
+    }
+
+    /// The player's current rating.
+    pub fn rating(&self, player: &str) -> f64 {
+        self.history(player)
+            .last()
+            .map_or(INITIAL, |entry| entry.rating)
+    }
+
+    pub fn history(&self, player: &str) -> &[Entry] {
+        self.players.get(player).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn record_solo(&mut self, player: &str, game: &Game, date: &str) -> Entry {
+        let rating = self.rating(player);
+        let change = K * (solo_score(game) - expected(rating, INITIAL));
+        self.push(player, rating, change, MatchKind::Solo, date)
+    }
+
+    /// Rates a match between several players. `places` gives each player's
+    /// finishing place, 1 for the winner; players sharing a place drew. Each
+    /// player plays every other, with the stake split between the pairs.
+    pub fn record_match(
+        &mut self,
+        kind: MatchKind,
+        places: &[(String, u32)],
+        date: &str,
+    ) -> Vec<Entry> {
+        if places.len() < 2 {
+            return Vec::new();
+        }
+        let before: Vec<f64> = places
+            .iter()
+            .map(|(player, _)| self.rating(player))
+            .collect();
+        let k = K / (places.len() - 1) as f64;
+        places
+            .iter()
+            .enumerate()
+            .map(|(i, (player, place))| {
+                let change: f64 = places
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(j, (_, other))| {
+                        let score = match place.cmp(other) {
+                            std::cmp::Ordering::Less => 1.0,
+                            std::cmp::Ordering::Equal => 0.5,
+                            std::cmp::Ordering::Greater => 0.0,
+                        };
+                        k * (score - expected(before[i], before[j]))
+                    })
+                    .sum();
+                self.push(player, before[i], change, kind, date)
+            })
+            .collect()
+    }
+
+    fn push(
+        &mut self,
+        player: &str,
+        rating: f64,
+        change: f64,
+        kind: MatchKind,
+        date: &str,
+    ) -> Entry {
+        let entry = Entry {
+            rating: rating + change,
+            change,
+            kind,
+            date: date.to_string(),
+        };
+        self.players
+            .entry(player.to_string())
+            .or_default()
+            .push(entry.clone());
+        entry
+    }
+}
+
+/// Draws the last [`CHART_WIDTH`] ratings of a history as a terminal chart
+/// `height` rows tall, with the rating scale on the left.
+pub fn chart(history: &[Entry], height: usize) -> String {
+    let ratings: Vec<f64> = history
+        .iter()
+        .rev()
+        .take(CHART_WIDTH)
+        .rev()
+        .map(|entry| entry.rating)
+        .collect();
+    if ratings.is_empty() || height < 2 {
+        return String::new();
+    }
+    let mut low = ratings.iter().copied().fold(f64::INFINITY, f64::min);
+    let mut high = ratings.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if high - low < 1.0 {
+        low -= 10.0;
+        high += 10.0;
+    }
+    let step = (high - low) / (height - 1) as f64;
+    let row_of = |rating: f64| ((rating - low) / step).round() as usize;
+
+    let mut out = String::new();
+    for row in (0..height).rev() {
+        out += &format!("{:>6.0} ┤", low + step * row as f64);
+        for &rating in &ratings {
+            out.push(if row_of(rating) == row { '•' } else { ' ' });
+        }
+        out = out.trim_end().to_string();
+        out.push('\n');
+    }
+    out += &format!("{:>6} └{}\n", "", "─".repeat(ratings.len()));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn won_in(attempts: u32) -> Game {
+        let mut game = Game::new(100, 1..=100);
+        for guess in 100 - attempts + 1..=100 {
+            game.guess(guess).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn solo_games_are_scored_against_par() {
+        // 1..=100 takes 7 guesses to bisect.
+        assert_eq!(solo_score(&won_in(7)), 0.5);
+        assert!(solo_score(&won_in(3)) > 0.5);
+        assert_eq!(solo_score(&won_in(14)), 0.0);
+        assert_eq!(solo_score(&Game::new(5, 1..=100)), 0.0);
+
+        let mut ratings = Ratings::default();
+        assert_eq!(
+            ratings.record_solo("ana", &won_in(7), "2026-10-17").change,
+            0.0
+        );
+        let entry = ratings.record_solo("ana", &won_in(2), "2026-10-17");
+        assert!(entry.change > 0.0);
+        assert_eq!(ratings.rating("ana"), entry.rating);
+        assert!(ratings.record_solo("ana", &won_in(12), "2026-10-17").change < 0.0);
+        assert_eq!(ratings.history("ana").len(), 3);
+        assert_eq!(ratings.rating("bo"), INITIAL);
+    }
+
+    #[test]
+    fn matches_move_points_from_losers_to_winners() {
+        let mut ratings = Ratings::default();
+        let places = [
+            ("ana".to_string(), 1),
+            ("bo".to_string(), 2),
+            ("cy".to_string(), 2),
+        ];
+        let entries = ratings.record_match(MatchKind::HotSeat, &places, "2026-10-17");
+        assert_eq!(entries[0].change, 16.0);
+        assert_eq!(entries[1].change, -8.0);
+        assert_eq!(entries[2].change, -8.0);
+        let total: f64 = entries.iter().map(|entry| entry.change).sum();
+        assert!(total.abs() < 1e-9);
+
+        // Beating a weaker player again is worth less.
+        let again = ratings.record_match(MatchKind::Network, &places[..2], "2026-10-18");
+        assert!(again[0].change < 16.0 && again[0].change > 0.0);
+        assert!(
+            ratings
+                .record_match(MatchKind::Network, &places[..1], "x")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn charts_plot_one_column_per_game() {
+        let mut ratings = Ratings::default();
+        for attempts in [2, 2, 14, 7] {
+            ratings.record_solo("ana", &won_in(attempts), "2026-10-17");
+        }
+        let chart = chart(ratings.history("ana"), 5);
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].ends_with("┤ •"), "{}", chart);
+        assert!(lines[5].ends_with("└────"), "{}", chart);
+        assert_eq!(chart.matches('•').count(), 4);
+        assert_eq!(super::chart(&[], 5), "");
+    }
+}
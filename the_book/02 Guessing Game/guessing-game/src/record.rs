@@ -0,0 +1,253 @@
+//! Session logs: everything needed to play a game back and check that the
+//! rules still give the same answers.
+//!
+//! A log is a JSON Lines file. The first line is a [`Header`] and every
+//! following line is an [`Entry`] for one line the player typed once the
+//! game had started. A line typed at the name prompt is kept in the header.
+
+use crate::hints::HintKind;
+use crate::{Game, Turn};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::time::Instant;
+
+/// How the recorded game was set up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    pub seed: u64,
+    pub min: u32,
+    pub max: u32,
+    pub max_attempts: Option<u32>,
+    #[serde(default)]
+    pub hints: Vec<HintKind>,
+    /// The line typed at the name prompt, if the player was asked.
+    #[serde(default)]
+    pub name_line: Option<String>,
+}
+
+impl Header {
+    /// The header for a game that was created with `Game::seeded(seed, ..)`
+    /// by a player who typed `name_line` when asked for a name.
+    pub fn new(seed: u64, game: &Game, name_line: Option<&str>) -> Header {
+        Header {
+            seed,
+            min: *game.range().start(),
+            max: *game.range().end(),
+            max_attempts: game.max_attempts(),
+            hints: game.hints().to_vec(),
+            name_line: name_line.map(str::to_string),
+        }
+    }
+
+    pub fn range(&self) -> RangeInclusive<u32> {
+        self.min..=self.max
+    }
+
+    /// A fresh game identical to the recorded one.
+    pub fn game(&self) -> Game {
+        let game = Game::seeded(self.seed, self.range()).with_hints(&self.hints);
+        match self.max_attempts {
+            Some(max) => game.with_max_attempts(max),
+            None => game,
+        }
+    }
+}
+
+/// One raw line of input, when it was typed and what it did.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Milliseconds since the game started.
+    pub at_ms: u64,
+    pub input: String,
+    /// What the line did to the game, or `None` for a line the game never
+    /// saw, like `:save`. A log that ends in one was cut short on purpose.
+    pub turn: Option<Turn>,
+}
+
+/// Writes a session log as the game is played, one flushed line per turn
+/// so an interrupted game still leaves a usable log.
+pub struct Recorder {
+    out: BufWriter<File>,
+    started: Instant,
+}
+
+impl Recorder {
+    pub fn create(path: &Path, header: &Header) -> io::Result<Recorder> {
+        let mut recorder = Recorder {
+            out: BufWriter::new(File::create(path)?),
+            started: Instant::now(),
+        };
+        recorder.write(header)?;
+        Ok(recorder)
+    }
+
+    /// Logs a line and, if the game played it, what it did.
+    pub fn record(&mut self, input: &str, turn: Option<&Turn>) -> io::Result<()> {
+        let entry = Entry {
+            at_ms: self.started.elapsed().as_millis() as u64,
+            input: input.to_string(),
+            turn: turn.cloned(),
+        };
+        self.write(&entry)
+    }
+
+    fn write<T: Serialize>(&mut self, value: &T) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, value)?;
+        self.out.write_all(b"\n")?;
+        self.out.flush()
+    }
+}
+
+/// A session log read back from disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recording {
+    pub header: Header,
+    pub entries: Vec<Entry>,
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> io::Result<Recording> {
+        Recording::read(BufReader::new(File::open(path)?))
+    }
+
+    pub fn read<R: BufRead>(input: R) -> io::Result<Recording> {
+        let mut lines = input.lines();
+        let header = match lines.next() {
+            Some(line) => parse(&line?)?,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the session log is empty",
+                ));
+            }
+        };
+        let entries = lines.map(|line| parse(&line?)).collect::<io::Result<_>>()?;
+        Ok(Recording { header, entries })
+    }
+
+    /// Plays every recorded line through a fresh game and returns the
+    /// first entry whose turn no longer matches, with what happens now.
+    /// Lines the game never saw are skipped.
+    pub fn verify(&self) -> Option<(usize, Turn)> {
+        let mut game = self.header.game();
+        self.entries.iter().enumerate().find_map(|(i, entry)| {
+            let recorded = entry.turn.as_ref()?;
+            let turn = game.take_turn(&entry.input);
+            (turn != *recorded).then_some((i, turn))
+        })
+    }
+
+    /// Whether the log ends with a line the game never saw, i.e. the game
+    /// was saved to be finished later rather than cut off.
+    pub fn ends_unplayed(&self) -> bool {
+        self.entries
+            .last()
+            .is_some_and(|entry| entry.turn.is_none())
+    }
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(line: &str) -> io::Result<T> {
+    serde_json::from_str(line).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GuessOutcome;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "guessing-game-{}-{}.jsonl",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn record(header: &Header, inputs: &[&str]) -> Recording {
+        let path = temp_path("record");
+        let mut recorder = Recorder::create(&path, header).unwrap();
+        let mut game = header.game();
+        for input in inputs {
+            if input.starts_with(':') {
+                recorder.record(input, None).unwrap();
+            } else {
+                let turn = game.take_turn(input);
+                recorder.record(input, Some(&turn)).unwrap();
+            }
+        }
+        drop(recorder);
+        let recording = Recording::load(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        recording
+    }
+
+    #[test]
+    fn recordings_round_trip_and_verify() {
+        let header = Header {
+            seed: 11,
+            min: 1,
+            max: 100,
+            max_attempts: Some(3),
+            hints: vec![HintKind::Parity],
+            name_line: Some("ana\n".to_string()),
+        };
+        let recording = record(
+            &header,
+            &["50\n", "oops\n", "hint parity\n", "0\n", "25\n", "75\n"],
+        );
+        assert_eq!(recording.header, header);
+        assert_eq!(recording.entries.len(), 6);
+        assert!(matches!(recording.entries[2].turn, Some(Turn::Hint(Ok(_)))));
+        assert_eq!(recording.entries[1].input, "oops\n");
+        assert!(
+            recording
+                .entries
+                .windows(2)
+                .all(|w| w[0].at_ms <= w[1].at_ms)
+        );
+        assert_eq!(recording.verify(), None);
+        assert!(!recording.ends_unplayed());
+    }
+
+    #[test]
+    fn saving_ends_the_log_on_an_unplayed_line() {
+        let header = Header::new(5, &Game::seeded(5, 1..=100), None);
+        let recording = record(&header, &["50\n", ":save x\n", "25\n", ":save\n"]);
+        assert_eq!(recording.entries[1].turn, None);
+        assert_eq!(recording.verify(), None);
+        assert!(recording.ends_unplayed());
+    }
+
+    #[test]
+    fn verify_reports_the_first_divergence() {
+        let header = Header {
+            seed: 3,
+            min: 1,
+            max: 10,
+            max_attempts: None,
+            hints: Vec::new(),
+            name_line: None,
+        };
+        let mut recording = record(&header, &["1\n", "10\n"]);
+        let secret = header.game().secret();
+        recording.entries[1].turn = Some(Turn::Guessed {
+            guess: 10,
+            outcome: if secret == 10 {
+                GuessOutcome::TooBig
+            } else {
+                GuessOutcome::Win
+            },
+        });
+        let (index, _) = recording.verify().unwrap();
+        assert_eq!(index, 1);
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(Recording::read("".as_bytes()).is_err());
+        assert!(Recording::read("not json\n".as_bytes()).is_err());
+    }
+}
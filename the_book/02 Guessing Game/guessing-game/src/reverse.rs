@@ -0,0 +1,267 @@
+//! Reverse mode: the player thinks of a number and the computer guesses it.
+
+use crate::GuessOutcome;
+use crate::i18n::{Lang, Localize};
+use crate::strategy;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Reads the player's reply to a guess as an `Ordering` of their secret
+/// compared with the guess: `higher` is `Greater`, `lower` is `Less` and
+/// `correct` is `Equal`.
+pub fn parse_answer(line: &str) -> Option<Ordering> {
+    match line.trim().to_ascii_lowercase().as_str() {
+        "h" | "higher" | "greater" | ">" => Some(Ordering::Greater),
+        "l" | "lower" | "less" | "<" => Some(Ordering::Less),
+        "c" | "correct" | "equal" | "=" => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+/// One of the player's replies, as the outcome of the computer's guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Answer {
+    pub guess: u32,
+    pub outcome: GuessOutcome,
+}
+
+impl Localize for Answer {
+    fn localize(&self, lang: Lang) -> String {
+        let guess = lang.number(self.guess);
+        match self.outcome {
+            GuessOutcome::TooSmall => lang.format("answer_higher", &[("guess", &guess)]),
+            GuessOutcome::TooBig => lang.format("answer_lower", &[("guess", &guess)]),
+            GuessOutcome::Win => lang.format("answer_exactly", &[("guess", &guess)]),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+/// Two replies that cannot both be true. Answers are numbered from 1 in
+/// the order they were given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contradiction {
+    pub number: usize,
+    pub answer: Answer,
+    /// The earlier answer it conflicts with, or `None` if it conflicts with
+    /// the range the player agreed to pick from.
+    pub earlier: Option<(usize, Answer)>,
+}
+
+impl Localize for Contradiction {
+    fn localize(&self, lang: Lang) -> String {
+        let answer = self.answer.localize(lang);
+        match &self.earlier {
+            Some((earlier, earlier_answer)) => lang.format(
+                "contradicts",
+                &[
+                    ("number", &self.number),
+                    ("answer", &answer),
+                    ("earlier", earlier),
+                    ("earlier_answer", &earlier_answer.localize(lang)),
+                ],
+            ),
+            None => lang.format(
+                "outside_range",
+                &[("number", &self.number), ("answer", &answer)],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+/// Finds the player's number by bisecting the interval that is still
+/// consistent with every answer.
+#[derive(Debug, Clone)]
+pub struct Guesser {
+    range: RangeInclusive<u32>,
+    low: u32,
+    high: u32,
+    /// Indexes of the answers that set `low` and `high`.
+    low_from: Option<usize>,
+    high_from: Option<usize>,
+    answers: Vec<Answer>,
+    solved: bool,
+}
+
+impl Guesser {
+    pub fn new(range: RangeInclusive<u32>) -> Guesser {
+        Guesser {
+            low: *range.start(),
+            high: *range.end(),
+            range,
+            low_from: None,
+            high_from: None,
+            answers: Vec::new(),
+            solved: false,
+        }
+    }
+
+    /// The midpoint of the numbers still possible, or `None` once the
+    /// player has said a guess was correct.
+    pub fn next_guess(&self) -> Option<u32> {
+        if self.solved {
+            None
+        } else {
+            Some(strategy::midpoint(self.low, self.high))
+        }
+    }
+
+    /// Applies the player's reply to `guess`, rejecting it if no number
+    /// could satisfy it together with the earlier replies.
+    pub fn answer(&mut self, guess: u32, reply: Ordering) -> Result<(), Contradiction> {
+        let answer = Answer {
+            guess,
+            // The player compares their secret with the guess; the game
+            // compares the guess with the secret.
+            outcome: reply.reverse().into(),
+        };
+        let index = self.answers.len();
+
+        let conflict = match answer.outcome {
+            GuessOutcome::TooSmall if guess >= self.high => Some(self.high_from),
+            GuessOutcome::TooBig if guess <= self.low => Some(self.low_from),
+            GuessOutcome::Win if guess < self.low => Some(self.low_from),
+            GuessOutcome::Win if guess > self.high => Some(self.high_from),
+            _ => None,
+        };
+        if let Some(earlier) = conflict {
+            return Err(Contradiction {
+                number: index + 1,
+                answer,
+                earlier: earlier.map(|i| (i + 1, self.answers[i])),
+            });
+        }
+
+        match answer.outcome {
+            GuessOutcome::TooSmall => {
+                self.low = guess + 1;
+                self.low_from = Some(index);
+            }
+            GuessOutcome::TooBig => {
+                self.high = guess - 1;
+                self.high_from = Some(index);
+            }
+            GuessOutcome::Win => {
+                self.low = guess;
+                self.high = guess;
+                self.solved = true;
+            }
+        }
+        self.answers.push(answer);
+        Ok(())
+    }
+
+    /// The numbers still consistent with every answer.
+    pub fn remaining(&self) -> RangeInclusive<u32> {
+        self.low..=self.high
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    pub fn answers(&self) -> &[Answer] {
+        &self.answers
+    }
+
+    /// The player's number, once they have confirmed it.
+    pub fn solution(&self) -> Option<u32> {
+        if self.solved { Some(self.low) } else { None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Game;
+
+    /// Plays the guesser against an honest player who picked `secret`.
+    fn solve(secret: u32, range: RangeInclusive<u32>) -> usize {
+        let mut guesser = Guesser::new(range);
+        while let Some(guess) = guesser.next_guess() {
+            guesser.answer(guess, secret.cmp(&guess)).unwrap();
+        }
+        assert_eq!(guesser.solution(), Some(secret));
+        guesser.answers().len()
+    }
+
+    #[test]
+    fn parses_replies() {
+        assert_eq!(parse_answer("higher\n"), Some(Ordering::Greater));
+        assert_eq!(parse_answer(" L "), Some(Ordering::Less));
+        assert_eq!(parse_answer("="), Some(Ordering::Equal));
+        assert_eq!(parse_answer("maybe"), None);
+    }
+
+    #[test]
+    fn bisection_finds_every_number_in_optimal_attempts() {
+        let optimal = crate::optimal_attempts(&(1..=100)) as usize;
+        for secret in 1..=100 {
+            assert!(solve(secret, 1..=100) <= optimal, "{}", secret);
+        }
+        assert!(solve(u32::MAX, 1..=u32::MAX) <= 32);
+        assert!(solve(1, 1..=u32::MAX) <= 32);
+    }
+
+    #[test]
+    fn answers_agree_with_the_game() {
+        let mut game = Game::new(37, 1..=100);
+        let mut guesser = Guesser::new(1..=100);
+        while let Some(guess) = guesser.next_guess() {
+            let reply = 37.cmp(&guess);
+            guesser.answer(guess, reply).unwrap();
+            assert_eq!(
+                guesser.answers().last().unwrap().outcome,
+                game.guess(guess).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn detects_contradicting_answers() {
+        let mut guesser = Guesser::new(1..=100);
+        guesser.answer(50, Ordering::Less).unwrap();
+        guesser.answer(25, Ordering::Greater).unwrap();
+        guesser.answer(37, Ordering::Greater).unwrap();
+        guesser.answer(43, Ordering::Less).unwrap();
+        guesser.answer(40, Ordering::Less).unwrap();
+        let err = guesser.answer(38, Ordering::Less).unwrap_err();
+        assert_eq!(err.number, 6);
+        assert_eq!(err.earlier.map(|(number, _)| number), Some(3));
+        assert_eq!(
+            err.to_string(),
+            "answer #6 (lower than 38) contradicts answer #3 (higher than 37)"
+        );
+    }
+
+    #[test]
+    fn correct_outside_the_interval_is_a_contradiction() {
+        let mut guesser = Guesser::new(1..=100);
+        guesser.answer(50, Ordering::Greater).unwrap();
+        let err = guesser.answer(20, Ordering::Equal).unwrap_err();
+        assert_eq!(err.earlier.map(|(number, _)| number), Some(1));
+    }
+
+    #[test]
+    fn answers_beyond_the_range_are_contradictions() {
+        let mut guesser = Guesser::new(1..=10);
+        let err = guesser.answer(10, Ordering::Greater).unwrap_err();
+        assert_eq!(err.earlier, None);
+        assert_eq!(
+            err.to_string(),
+            "answer #1 (higher than 10) is outside the range you picked from"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Saved games: a paused game written to a file so it can be resumed later.
+//!
+//! The secret is stored masked with a per-file salt, so opening the file
+//! does not give the answer away at a glance, and the whole file carries a
+//! checksum so that edits are noticed. Neither is meant to stop a
+//! determined cheat, only accidents and casual peeking.
+
+use crate::hints::HintKind;
+use crate::i18n::{Lang, Localize};
+use crate::{Game, Status};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// Bumped whenever the layout of a save file changes.
+const VERSION: u32 = 1;
+
+/// Mixed into the checksum so that it cannot be recomputed by hashing the
+/// file with a stock tool.
+const KEY: &[u8] = b"guessing-game save v1";
+
+/// Everything needed to pick a game up where it was left.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub player: String,
+    /// The leaderboard category the game counts under.
+    pub difficulty: String,
+    pub min: u32,
+    pub max: u32,
+    pub max_attempts: Option<u32>,
+    pub hints: Vec<HintKind>,
+    pub hints_used: Vec<HintKind>,
+    pub history: Vec<u32>,
+    pub elapsed_ms: u64,
+    salt: u64,
+    /// The secret, masked with a value derived from `salt`.
+    secret: String,
+    checksum: String,
+}
+
+/// Why a save file could not be loaded.
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    /// The file is not a save file at all, or is truncated.
+    Corrupt(String),
+    /// The file was written in a layout this version cannot read.
+    Version(u32),
+    /// The file was edited after it was saved.
+    Tampered,
+    /// The file is intact but describes a game that cannot exist.
+    Invalid(InvalidGame),
+}
+
+/// What is impossible about the game an intact save file describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidGame {
+    UnreadableSecret,
+    SecretOutOfRange,
+    ImpossibleGuess,
+    GameOver,
+}
+
+impl Localize for SaveError {
+    fn localize(&self, lang: Lang) -> String {
+        match self {
+            SaveError::Io(err) => err.to_string(),
+            SaveError::Corrupt(reason) => lang.format("save_corrupt", &[("reason", reason)]),
+            SaveError::Version(version) => lang.format("save_version", &[("version", version)]),
+            SaveError::Tampered => lang.format("save_tampered", &[]),
+            SaveError::Invalid(reason) => {
+                lang.format("save_invalid", &[("reason", &reason.localize(lang))])
+            }
+        }
+    }
+}
+
+impl Localize for InvalidGame {
+    fn localize(&self, lang: Lang) -> String {
+        match self {
+            InvalidGame::UnreadableSecret => lang.format("secret_unreadable", &[]),
+            InvalidGame::SecretOutOfRange => lang.format("secret_outside_range", &[]),
+            InvalidGame::ImpossibleGuess => lang.format("impossible_guess", &[]),
+            InvalidGame::GameOver => lang.format("game_over", &[]),
+        }
+    }
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> SaveError {
+        SaveError::Io(err)
+    }
+}
+
+impl SaveFile {
+    /// Captures a game in progress.
+    pub fn new(game: &Game, player: &str, difficulty: &str, elapsed: Duration) -> SaveFile {
+        let salt = rand::random();
+        let mut save = SaveFile {
+            version: VERSION,
+            player: player.to_string(),
+            difficulty: difficulty.to_string(),
+            min: *game.range().start(),
+            max: *game.range().end(),
+            max_attempts: game.max_attempts(),
+            hints: game.hints().to_vec(),
+            hints_used: game.hints_used().to_vec(),
+            history: game.history().to_vec(),
+            elapsed_ms: elapsed.as_millis() as u64,
+            salt,
+            secret: format!("{:08x}", game.secret() ^ mask(salt)),
+            checksum: String::new(),
+        };
+        save.checksum = save.compute_checksum();
+        save
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_vec_pretty(self)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        fs::write(path, json)
+    }
+
+    /// Reads a save file and checks it has not been tampered with.
+    pub fn load(path: &Path) -> Result<SaveFile, SaveError> {
+        let bytes = fs::read(path)?;
+        let save: SaveFile =
+            serde_json::from_slice(&bytes).map_err(|err| SaveError::Corrupt(err.to_string()))?;
+        if save.version != VERSION {
+            return Err(SaveError::Version(save.version));
+        }
+        if save.checksum != save.compute_checksum() {
+            return Err(SaveError::Tampered);
+        }
+        Ok(save)
+    }
+
+    /// Rebuilds the game, replaying its guesses against the secret.
+    pub fn game(&self) -> Result<Game, SaveError> {
+        let secret = u32::from_str_radix(&self.secret, 16)
+            .map_err(|_| SaveError::Invalid(InvalidGame::UnreadableSecret))?
+            ^ mask(self.salt);
+        let range = self.min..=self.max;
+        if !range.contains(&secret) {
+            return Err(SaveError::Invalid(InvalidGame::SecretOutOfRange));
+        }
+
+        let mut game = Game::new(secret, range).with_hints(&self.hints);
+        if let Some(max) = self.max_attempts {
+            game = game.with_max_attempts(max);
+        }
+        for &guess in &self.history {
+            game.guess(guess)
+                .map_err(|_| SaveError::Invalid(InvalidGame::ImpossibleGuess))?;
+        }
+        if game.status() != Status::Playing {
+            return Err(SaveError::Invalid(InvalidGame::GameOver));
+        }
+        game.restore_hints_used(&self.hints_used);
+        Ok(game)
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.elapsed_ms)
+    }
+
+    fn compute_checksum(&self) -> String {
+        let unsigned = SaveFile {
+            checksum: String::new(),
+            ..self.clone()
+        };
+        let json = serde_json::to_vec(&unsigned).expect("a save file always serializes");
+        format!("{:016x}", digest(KEY.iter().chain(&json)))
+    }
+}
+
+/// Derives the value the secret is masked with from the file's salt.
+fn mask(salt: u64) -> u32 {
+    (splitmix(salt) >> 32) as u32
+}
+
+/// FNV-1a followed by a final mix, enough to notice any edit.
+fn digest<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u64 {
+    let hash = bytes
+        .into_iter()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    splitmix(hash)
+}
+
+fn splitmix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "guessing-game-{}-{}.save",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn saved_game() -> SaveFile {
+        let mut game = Game::new(42, 1..=100)
+            .with_max_attempts(7)
+            .with_hints(&[HintKind::Parity]);
+        game.guess(50).unwrap();
+        game.request_hint(HintKind::Parity).unwrap();
+        game.guess(25).unwrap();
+        SaveFile::new(&game, "ana", "normal", Duration::from_millis(1500))
+    }
+
+    #[test]
+    fn saved_games_resume_where_they_left_off() {
+        let path = temp_path("resume");
+        saved_game().write(&path).unwrap();
+        let save = SaveFile::load(&path).unwrap();
+        let game = save.game().unwrap();
+        assert_eq!(game.secret(), 42);
+        assert_eq!(game.history(), [50, 25]);
+        assert_eq!(game.attempts_left(), Some(5));
+        assert_eq!(game.hints_used(), [HintKind::Parity]);
+        assert_eq!(save.elapsed(), Duration::from_millis(1500));
+        assert_eq!(save.player, "ana");
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn the_secret_is_not_stored_in_the_clear() {
+        let save = saved_game();
+        assert_ne!(u32::from_str_radix(&save.secret, 16).unwrap(), 42);
+        assert_eq!(save.game().unwrap().secret(), 42);
+    }
+
+    #[test]
+    fn edited_files_are_rejected() {
+        let path = temp_path("tampered");
+        saved_game().write(&path).unwrap();
+        let json = fs::read_to_string(&path).unwrap();
+        fs::write(&path, json.replace("\"max\": 100", "\"max\": 1000")).unwrap();
+        assert!(matches!(SaveFile::load(&path), Err(SaveError::Tampered)));
+
+        fs::write(&path, &json[..json.len() / 2]).unwrap();
+        assert!(matches!(SaveFile::load(&path), Err(SaveError::Corrupt(_))));
+        let _ = fs::remove_file(path);
+    }
+}
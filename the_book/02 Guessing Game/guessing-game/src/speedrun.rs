@@ -0,0 +1,150 @@
+//! Speedrun mode: the usual game against the clock. Each guess is timed
+//! from the prompt to the answer, and the score rewards finishing quickly
+//! as well as in few attempts.
+
+use crate::game::{Game, Status, Turn, optimal_attempts};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// The time a brisk player needs per guess of a perfect bisection.
+pub const PAR_PER_GUESS: Duration = Duration::from_secs(5);
+
+/// The time that halves the score for a range.
+pub fn par(range: &RangeInclusive<u32>) -> Duration {
+    PAR_PER_GUESS * optimal_attempts(range)
+}
+
+/// Scales a game's score by how long it took: a win in par time keeps half
+/// the points, and the faster the win the closer it gets to all of them.
+pub fn score(game: &Game, elapsed: Duration) -> u64 {
+    let par = par(game.range()).as_millis();
+    (u128::from(game.score()) * par / (par + elapsed.as_millis())) as u64
+}
+
+/// Formats a duration as a stopwatch reading, e.g. `01:07.3`.
+pub fn clock(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    format!(
+        "{:02}:{:02}.{}",
+        seconds / 60,
+        seconds % 60,
+        elapsed.subsec_millis() / 100
+    )
+}
+
+/// A player's best speedrun at one difficulty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Best {
+    pub score: u64,
+    pub time_ms: u64,
+    pub attempts: u32,
+}
+
+/// A game together with how long each guess took.
+#[derive(Debug, Clone)]
+pub struct Speedrun {
+    game: Game,
+    limit: Option<Duration>,
+    latencies: Vec<Duration>,
+    timed_out: bool,
+}
+
+impl Speedrun {
+    pub fn new(game: Game, limit: Option<Duration>) -> Speedrun {
+        Speedrun {
+            game,
+            limit,
+            latencies: Vec::new(),
+            timed_out: false,
+        }
+    }
+
+    /// Plays one line of input that took `latency` to arrive.
+    pub fn take_turn(&mut self, line: &str, latency: Duration) -> Turn {
+        let turn = self.game.take_turn(line);
+        if let Turn::Guessed { .. } = turn {
+            self.latencies.push(latency);
+        }
+        turn
+    }
+
+    /// Ends the game because the time limit ran out.
+    pub fn time_up(&mut self) {
+        self.timed_out = true;
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn limit(&self) -> Option<Duration> {
+        self.limit
+    }
+
+    /// How long each counted guess took, oldest first.
+    pub fn latencies(&self) -> &[Duration] {
+        &self.latencies
+    }
+
+    pub fn status(&self) -> Status {
+        match self.game.status() {
+            Status::Playing if self.timed_out => Status::Lost,
+            status => status,
+        }
+    }
+
+    /// The personal best entry this run would make, if it was won.
+    pub fn best(&self, elapsed: Duration) -> Option<Best> {
+        (self.status() == Status::Won).then(|| Best {
+            score: score(&self.game, elapsed),
+            time_ms: elapsed.as_millis() as u64,
+            attempts: self.game.attempts(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faster_wins_score_higher() {
+        let mut game = Game::new(50, 1..=100).with_max_attempts(10);
+        game.guess(50).unwrap();
+        let par = par(game.range());
+        assert_eq!(par, Duration::from_secs(35));
+        assert_eq!(score(&game, Duration::ZERO), game.score());
+        assert_eq!(score(&game, par), game.score() / 2);
+        assert!(score(&game, Duration::from_secs(5)) > score(&game, Duration::from_secs(6)));
+    }
+
+    #[test]
+    fn only_guesses_are_timed() {
+        let mut run = Speedrun::new(Game::new(7, 1..=10), None);
+        run.take_turn("abc", Duration::from_secs(9));
+        run.take_turn("3", Duration::from_millis(800));
+        run.take_turn("7", Duration::from_millis(600));
+        assert_eq!(
+            run.latencies(),
+            [Duration::from_millis(800), Duration::from_millis(600)]
+        );
+        assert_eq!(run.status(), Status::Won);
+        assert_eq!(run.best(Duration::from_secs(2)).unwrap().attempts, 2);
+    }
+
+    #[test]
+    fn running_out_of_time_loses() {
+        let mut run = Speedrun::new(Game::new(7, 1..=10), Some(Duration::from_secs(1)));
+        run.take_turn("3", Duration::from_millis(500));
+        run.time_up();
+        assert_eq!(run.status(), Status::Lost);
+        assert_eq!(run.best(Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn clocks_show_tenths() {
+        assert_eq!(clock(Duration::from_millis(67_345)), "01:07.3");
+        assert_eq!(clock(Duration::ZERO), "00:00.0");
+    }
+}
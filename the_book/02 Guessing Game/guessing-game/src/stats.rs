@@ -0,0 +1,387 @@
+use crate::achievements::{self, Achievement, Context};
+use crate::daily::{DailyResult, Date};
+use crate::game::Game;
+use crate::i18n::{Lang, Localize};
+use crate::speedrun::{self, Best};
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Results for one player at one difficulty.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub played: u32,
+    pub wins: u32,
+    /// The fewest attempts needed for a win.
+    pub best: Option<u32>,
+    pub total_attempts: u64,
+    /// Wins in a row, up to and including the latest game.
+    #[serde(default)]
+    pub streak: u32,
+}
+
+impl Record {
+    pub fn add(&mut self, won: bool, attempts: u32) {
+        self.played += 1;
+        self.total_attempts += u64::from(attempts);
+        if won {
+            self.wins += 1;
+            self.streak += 1;
+            self.best = Some(self.best.map_or(attempts, |best| best.min(attempts)));
+        } else {
+            self.streak = 0;
+        }
+    }
+
+    /// Mean attempts over every game played, won or lost.
+    pub fn average_attempts(&self) -> f64 {
+        if self.played == 0 {
+            0.0
+        } else {
+            self.total_attempts as f64 / f64::from(self.played)
+        }
+    }
+}
+
+/// Every player's records, keyed by player name and then difficulty.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub players: BTreeMap<String, BTreeMap<String, Record>>,
+    /// Daily challenge results, keyed by player name and then date.
+    #[serde(default)]
+    pub daily: BTreeMap<String, BTreeMap<String, DailyResult>>,
+    /// Speedrun personal bests, keyed by player name and then difficulty.
+    #[serde(default)]
+    pub speedruns: BTreeMap<String, BTreeMap<String, Best>>,
+    /// Unlocked achievements, keyed by player name and then achievement id,
+    /// with the date each was unlocked.
+    #[serde(default)]
+    pub achievements: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Leaderboard {
+    /// Where the leaderboard lives unless a caller picks another file.
+    pub fn default_path() -> Option<PathBuf> {
+        storage::data_dir().map(|dir| dir.join("leaderboard.json"))
+    }
+
+    pub fn load(path: &Path) -> io::Result<Leaderboard> {
+        storage::load_json(path)
+    }
+
+    /// Adds one finished game to the file at `path`, safe against other
+    /// processes doing the same.
+    pub fn record_game(
+        path: &Path,
+        player: &str,
+        difficulty: &str,
+        won: bool,
+        attempts: u32,
+    ) -> io::Result<Leaderboard> {
+        storage::update_json(path, |board: &mut Leaderboard| {
+            board.record(player, difficulty, won, attempts)
+        })
+    }
+
+    pub fn record(&mut self, player: &str, difficulty: &str, won: bool, attempts: u32) {
+        self.players
+            .entry(player.to_string())
+            .or_default()
+            .entry(difficulty.to_string())
+            .or_default()
+            .add(won, attempts);
+    }
+
+    pub fn get(&self, player: &str, difficulty: &str) -> Option<&Record> {
+        self.players.get(player)?.get(difficulty)
+    }
+
+    /// Marks the daily puzzle for `date` as started by `player`. Returns the
+    /// earlier result instead if they have already started it.
+    pub fn start_daily(path: &Path, player: &str, date: &str) -> io::Result<Option<DailyResult>> {
+        let mut earlier = None;
+        storage::update_json(path, |board: &mut Leaderboard| {
+            let days = board.daily.entry(player.to_string()).or_default();
+            earlier = days.get(date).cloned();
+            days.entry(date.to_string()).or_default();
+        })?;
+        Ok(earlier)
+    }
+
+    /// Stores the outcome of a daily puzzle and counts it under the `daily`
+    /// difficulty.
+    pub fn finish_daily(
+        path: &Path,
+        player: &str,
+        date: &str,
+        result: DailyResult,
+    ) -> io::Result<Leaderboard> {
+        storage::update_json(path, |board: &mut Leaderboard| {
+            board.record(player, "daily", result.won, result.attempts);
+            board
+                .daily
+                .entry(player.to_string())
+                .or_default()
+                .insert(date.to_string(), result);
+        })
+    }
+
+    /// Unlocks every achievement `game` earned `player` that they do not
+    /// have yet. Call it after the game has been recorded, so that rules see
+    /// it in the record. Returns the newly unlocked ones.
+    pub fn award(
+        path: &Path,
+        player: &str,
+        difficulty: &str,
+        game: &Game,
+    ) -> io::Result<Vec<&'static Achievement>> {
+        let mut unlocked = Vec::new();
+        storage::update_json(path, |board: &mut Leaderboard| {
+            unlocked = board.unlock(player, difficulty, game, &Date::today().to_string());
+        })?;
+        Ok(unlocked)
+    }
+
+    pub fn unlock(
+        &mut self,
+        player: &str,
+        difficulty: &str,
+        game: &Game,
+        date: &str,
+    ) -> Vec<&'static Achievement> {
+        let record = self.get(player, difficulty).cloned().unwrap_or_default();
+        let context = Context {
+            game,
+            record: &record,
+        };
+        let owned = self.achievements.entry(player.to_string()).or_default();
+        let mut unlocked = Vec::new();
+        for achievement in achievements::earned(&context) {
+            if !owned.contains_key(achievement.id) {
+                owned.insert(achievement.id.to_string(), date.to_string());
+                unlocked.push(achievement);
+            }
+        }
+        unlocked
+    }
+
+    /// Records a speedrun game, keeping `best` if it beats the player's
+    /// personal best at `difficulty`. Returns the personal best from before.
+    pub fn record_speedrun(
+        path: &Path,
+        player: &str,
+        difficulty: &str,
+        attempts: u32,
+        best: Option<Best>,
+    ) -> io::Result<Option<Best>> {
+        let mut earlier = None;
+        storage::update_json(path, |board: &mut Leaderboard| {
+            board.record(player, difficulty, best.is_some(), attempts);
+            let bests = board.speedruns.entry(player.to_string()).or_default();
+            earlier = bests.get(difficulty).copied();
+            if let Some(best) = best
+                && earlier.is_none_or(|earlier| best.score > earlier.score)
+            {
+                bests.insert(difficulty.to_string(), best);
+            }
+        })?;
+        Ok(earlier)
+    }
+}
+
+impl Localize for Leaderboard {
+    fn localize(&self, lang: Lang) -> String {
+        if self.players.is_empty() {
+            return format!("{}\n", lang.format("no_games_recorded", &[]));
+        }
+        let mut out = format!(
+            "{:<16} {:<10} {:>6} {:>6} {:>6} {:>8}\n",
+            lang.format("column_player", &[]),
+            lang.format("column_difficulty", &[]),
+            lang.format("column_played", &[]),
+            lang.format("column_wins", &[]),
+            lang.format("column_best", &[]),
+            lang.format("column_average", &[])
+        );
+        for (player, records) in &self.players {
+            for (difficulty, record) in records {
+                let best = record
+                    .best
+                    .map_or("-".to_string(), |best| lang.number(best));
+                let _ = writeln!(
+                    out,
+                    "{:<16} {:<10} {:>6} {:>6} {:>6} {:>8.1}",
+                    player,
+                    difficulty,
+                    lang.number(record.played),
+                    lang.number(record.wins),
+                    best,
+                    record.average_attempts()
+                );
+            }
+        }
+        if self.achievements.values().any(|owned| !owned.is_empty()) {
+            let _ = writeln!(
+                out,
+                "\n{:<16} {}",
+                lang.format("column_player", &[]),
+                lang.format("column_achievements", &[])
+            );
+            for (player, owned) in &self.achievements {
+                let names: Vec<String> = owned
+                    .keys()
+                    .filter_map(|id| achievements::find(id))
+                    .map(|achievement| (achievement.name)(lang))
+                    .collect();
+                if !names.is_empty() {
+                    let _ = writeln!(out, "{:<16} {}", player, names.join(", "));
+                }
+            }
+        }
+        if self.speedruns.values().any(|bests| !bests.is_empty()) {
+            let _ = writeln!(
+                out,
+                "\n{:<16} {:<10} {:>6} {:>8} {:>6}",
+                lang.format("column_speedrunner", &[]),
+                lang.format("column_difficulty", &[]),
+                lang.format("column_score", &[]),
+                lang.format("column_time", &[]),
+                lang.format("column_tries", &[])
+            );
+            for (player, bests) in &self.speedruns {
+                for (difficulty, best) in bests {
+                    let _ = writeln!(
+                        out,
+                        "{:<16} {:<10} {:>6} {:>8} {:>6}",
+                        player,
+                        difficulty,
+                        lang.number(best.score),
+                        speedrun::clock(Duration::from_millis(best.time_ms)),
+                        lang.number(best.attempts)
+                    );
+                }
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("guessing-game-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join("leaderboard.json")
+    }
+
+    #[test]
+    fn records_wins_losses_and_best() {
+        let mut board = Leaderboard::default();
+        board.record("ana", "normal", true, 6);
+        board.record("ana", "normal", false, 10);
+        board.record("ana", "normal", true, 4);
+        board.record("ana", "easy", true, 2);
+
+        let normal = board.get("ana", "normal").unwrap();
+        assert_eq!(normal.played, 3);
+        assert_eq!(normal.wins, 2);
+        assert_eq!(normal.best, Some(4));
+        assert!((normal.average_attempts() - 20.0 / 3.0).abs() < 1e-9);
+        assert_eq!(board.get("ana", "easy").unwrap().played, 1);
+        assert!(board.get("bo", "normal").is_none());
+    }
+
+    #[test]
+    fn table_lists_every_record() {
+        let mut board = Leaderboard::default();
+        assert_eq!(board.to_string(), "No games recorded yet.\n");
+        board.record("ana", "hard", false, 16);
+        let table = board.to_string();
+        assert!(table.lines().nth(1).unwrap().starts_with("ana"));
+        assert!(table.contains("16.0"));
+    }
+
+    #[test]
+    fn missing_file_loads_empty_and_round_trips() {
+        let path = temp_path("round-trip");
+        assert_eq!(Leaderboard::load(&path).unwrap(), Leaderboard::default());
+        let saved = Leaderboard::record_game(&path, "ana", "easy", true, 3).unwrap();
+        assert_eq!(Leaderboard::load(&path).unwrap(), saved);
+    }
+
+    #[test]
+    fn concurrent_updates_are_not_lost() {
+        let path = temp_path("concurrent");
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    for _ in 0..5 {
+                        Leaderboard::record_game(&path, "ana", "normal", i % 2 == 0, 5).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        let record = Leaderboard::load(&path)
+            .unwrap()
+            .get("ana", "normal")
+            .cloned()
+            .unwrap();
+        assert_eq!(record.played, 40);
+        assert_eq!(record.wins, 20);
+    }
+
+    #[test]
+    fn speedruns_keep_the_best_score() {
+        let path = temp_path("speedrun");
+        let run = |score| Best {
+            score,
+            time_ms: 9000,
+            attempts: 5,
+        };
+        let record = |best| Leaderboard::record_speedrun(&path, "ana", "normal", 5, best).unwrap();
+        assert_eq!(record(Some(run(300))), None);
+        assert_eq!(record(Some(run(200))), Some(run(300)));
+        assert_eq!(record(None), Some(run(300)));
+        assert_eq!(record(Some(run(400))), Some(run(300)));
+
+        let board = Leaderboard::load(&path).unwrap();
+        assert_eq!(board.speedruns["ana"]["normal"], run(400));
+        assert_eq!(board.get("ana", "normal").unwrap().played, 4);
+        assert!(board.to_string().contains("00:09.0"));
+    }
+
+    #[test]
+    fn achievements_unlock_once() {
+        let mut board = Leaderboard::default();
+        let mut game = Game::new(50, 1..=100);
+        game.guess(50).unwrap();
+        board.record("ana", "normal", true, 1);
+        let first = board.unlock("ana", "normal", &game, "2026-10-17");
+        assert_eq!(first.len(), 2);
+        assert!(
+            board
+                .unlock("ana", "normal", &game, "2026-10-18")
+                .is_empty()
+        );
+        assert_eq!(board.achievements["ana"]["first_try"], "2026-10-17");
+        assert!(board.to_string().contains("Mind Reader, Binary Searcher"));
+    }
+}
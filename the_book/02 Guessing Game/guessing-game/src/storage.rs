@@ -0,0 +1,110 @@
+//! Files the game keeps between runs, stored under the XDG data directory.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait for another process to release a data file.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `$XDG_DATA_HOME/guessing-game`, falling back to
+/// `~/.local/share/guessing-game` when the variable is unset.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(base.join("guessing-game"))
+}
+
+/// Reads a JSON file, treating a missing file as the default value.
+pub fn load_json<T: DeserializeOwned + Default>(path: &Path) -> io::Result<T> {
+    match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Writes a JSON file by renaming a fully written temporary file over it,
+/// so readers never see a half-written file.
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = sibling(path, &format!("{}.tmp", process::id()));
+    let json = serde_json::to_vec_pretty(value)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let mut file = File::create(&tmp)?;
+    file.write_all(&json)?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)
+}
+
+/// Loads, modifies and saves a JSON file while holding its lock, so two
+/// game processes finishing at once do not overwrite each other's results.
+pub fn update_json<T, F>(path: &Path, update: F) -> io::Result<T>
+where
+    T: Serialize + DeserializeOwned + Default,
+    F: FnOnce(&mut T),
+{
+    let _lock = Lock::acquire(path)?;
+    let mut value = load_json(path)?;
+    update(&mut value);
+    save_json(path, &value)?;
+    Ok(value)
+}
+
+/// An exclusive `<file>.lock` next to a data file, removed when dropped.
+struct Lock {
+    path: PathBuf,
+}
+
+impl Lock {
+    fn acquire(target: &Path) -> io::Result<Lock> {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let path = sibling(target, "lock");
+        let started = Instant::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(Lock { path }),
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                    if started.elapsed() > LOCK_TIMEOUT {
+                        return Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!(
+                                "{} is locked, remove it if no other game is running",
+                                path.display()
+                            ),
+                        ));
+                    }
+                    thread::sleep(Duration::from_millis(5));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// `dir/name.json` -> `dir/name.json.<suffix>`
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
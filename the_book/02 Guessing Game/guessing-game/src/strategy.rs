@@ -0,0 +1,159 @@
+//! Automated guessers, used by the benchmark harness and reverse mode.
+
+use rand::distributions::Uniform;
+use rand::prng::ChaChaRng;
+use rand::{Rng, SeedableRng};
+
+/// Picks guesses for an automated player.
+///
+/// The caller keeps track of the numbers that are still consistent with
+/// the feedback so far and passes them in as `low..=high`.
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    /// The next guess, which should lie within `low..=high`.
+    fn next_guess(&mut self, low: u32, high: u32) -> u32;
+}
+
+/// The middle of `low..=high`, rounding down.
+pub fn midpoint(low: u32, high: u32) -> u32 {
+    low + (high - low) / 2
+}
+
+/// Tries every number in order, starting with the smallest.
+#[derive(Debug, Clone, Default)]
+pub struct LinearScan;
+
+impl Strategy for LinearScan {
+    fn name(&self) -> &str {
+        "linear"
+    }
+
+    fn next_guess(&mut self, low: u32, _high: u32) -> u32 {
+        low
+    }
+}
+
+/// Guesses uniformly at random among the numbers still possible.
+#[derive(Debug, Clone)]
+pub struct RandomGuess {
+    rng: ChaChaRng,
+}
+
+impl RandomGuess {
+    pub fn new(seed: u64) -> RandomGuess {
+        RandomGuess {
+            rng: ChaChaRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for RandomGuess {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn next_guess(&mut self, low: u32, high: u32) -> u32 {
+        self.rng.sample(Uniform::new_inclusive(low, high))
+    }
+}
+
+/// Splits the remaining numbers in half, the optimal strategy.
+#[derive(Debug, Clone, Default)]
+pub struct Bisection;
+
+impl Strategy for Bisection {
+    fn name(&self) -> &str {
+        "bisection"
+    }
+
+    fn next_guess(&mut self, low: u32, high: u32) -> u32 {
+        midpoint(low, high)
+    }
+}
+
+/// Splits the remaining numbers at a fixed fraction instead of the middle.
+#[derive(Debug, Clone)]
+pub struct BiasedBisection {
+    bias: f64,
+}
+
+impl BiasedBisection {
+    /// `bias` is the fraction of the interval below each guess, clamped to
+    /// `0.0..=1.0`; `0.5` behaves like plain bisection.
+    pub fn new(bias: f64) -> BiasedBisection {
+        BiasedBisection {
+            bias: bias.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl Default for BiasedBisection {
+    fn default() -> BiasedBisection {
+        BiasedBisection::new(1.0 / 3.0)
+    }
+}
+
+impl Strategy for BiasedBisection {
+    fn name(&self) -> &str {
+        "biased"
+    }
+
+    fn next_guess(&mut self, low: u32, high: u32) -> u32 {
+        low + (f64::from(high - low) * self.bias) as u32
+    }
+}
+
+/// The names accepted by [`by_name`], in the order [`all`] returns them.
+pub const NAMES: [&str; 4] = ["linear", "random", "bisection", "biased"];
+
+/// Builds a strategy from its name; `seed` drives the random strategy.
+pub fn by_name(name: &str, seed: u64) -> Option<Box<dyn Strategy>> {
+    let strategy: Box<dyn Strategy> = match name {
+        "linear" => Box::new(LinearScan),
+        "random" => Box::new(RandomGuess::new(seed)),
+        "bisection" => Box::new(Bisection),
+        "biased" => Box::new(BiasedBisection::default()),
+        _ => return None,
+    };
+    Some(strategy)
+}
+
+/// One of every strategy.
+pub fn all(seed: u64) -> Vec<Box<dyn Strategy>> {
+    NAMES
+        .iter()
+        .filter_map(|name| by_name(name, seed))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guesses_stay_in_the_interval() {
+        for mut strategy in all(7) {
+            for &(low, high) in &[(1, 1), (1, 2), (5, 100), (0, u32::MAX)] {
+                let guess = strategy.next_guess(low, high);
+                assert!((low..=high).contains(&guess), "{}", strategy.name());
+            }
+        }
+    }
+
+    #[test]
+    fn names_match() {
+        for (strategy, name) in all(0).iter().zip(NAMES.iter()) {
+            assert_eq!(strategy.name(), *name);
+        }
+        assert!(by_name("psychic", 0).is_none());
+    }
+
+    #[test]
+    fn bisection_and_bias_split_where_expected() {
+        assert_eq!(Bisection.next_guess(1, 100), 50);
+        assert_eq!(Bisection.next_guess(0, u32::MAX), u32::MAX / 2);
+        assert_eq!(BiasedBisection::new(0.25).next_guess(0, 100), 25);
+        assert_eq!(BiasedBisection::new(2.0).next_guess(0, 100), 100);
+    }
+}
@@ -0,0 +1,379 @@
+//! Whether to colour the output, and which colours to use.
+//!
+//! A [`Theme`] gives each kind of message a [`Style`], so a palette can be
+//! swapped without touching the code that prints. Besides the classic
+//! green-and-red default there is a palette built from the Okabe-Ito colours,
+//! which stay distinct for the common kinds of colour blindness. Any other
+//! palette can be written as a JSON file, e.g.
+//!
+//! ```json
+//! { "win": { "color": "#0072B2", "bold": true }, "too_high": { "color": "magenta" } }
+//! ```
+//!
+//! Kinds the file leaves out keep their default style.
+
+use crate::game::GuessOutcome;
+use crate::i18n::{Lang, Localize};
+use crate::storage;
+use crate::wordle::Mark;
+use colored::{Color, ColoredString, Colorize};
+use serde::{Deserialize, Deserializer};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The `--color` setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Colour a terminal, unless the environment says otherwise.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether stdout should be coloured, looking at the real terminal and
+    /// environment.
+    pub fn enabled(self) -> bool {
+        self.resolve(io::stdout().is_terminal(), |var| env::var(var).ok())
+    }
+
+    /// Whether to colour, given if stdout is a terminal and a way to read
+    /// environment variables. `--color` always wins. Otherwise a non-empty
+    /// `NO_COLOR` turns colour off, then a `CLICOLOR_FORCE` other than `0`
+    /// turns it on even for pipes, and `CLICOLOR=0` turns it off.
+    pub fn resolve(self, terminal: bool, var: impl Fn(&str) -> Option<String>) -> bool {
+        let no_color = var("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let forced = var("CLICOLOR_FORCE").is_some_and(|value| !value.is_empty() && value != "0");
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto if no_color => false,
+            ColorChoice::Auto if forced => true,
+            ColorChoice::Auto => terminal && var("CLICOLOR").as_deref() != Some("0"),
+        }
+    }
+}
+
+/// The `--color` value was not auto, always or never.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorChoiceError(pub String);
+
+impl Localize for ParseColorChoiceError {
+    fn localize(&self, lang: Lang) -> String {
+        lang.format("unknown_color", &[("value", &self.0)])
+    }
+}
+
+impl fmt::Display for ParseColorChoiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for ParseColorChoiceError {}
+
+impl FromStr for ColorChoice {
+    type Err = ParseColorChoiceError;
+
+    fn from_str(s: &str) -> Result<ColorChoice, ParseColorChoiceError> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(ParseColorChoiceError(s.to_string())),
+        }
+    }
+}
+
+/// The kinds of message a theme styles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    TooHigh,
+    TooLow,
+    /// A wrong guess with no direction, like a code's bulls and cows.
+    Miss,
+    Lose,
+    Error,
+    Hint,
+}
+
+impl From<GuessOutcome> for Outcome {
+    fn from(outcome: GuessOutcome) -> Outcome {
+        match outcome {
+            GuessOutcome::Win => Outcome::Win,
+            GuessOutcome::TooBig => Outcome::TooHigh,
+            GuessOutcome::TooSmall => Outcome::TooLow,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Style {
+    /// A colour name such as `green` or `bright blue`, or `#rrggbb`.
+    #[serde(deserialize_with = "color")]
+    pub color: Option<Color>,
+    pub bold: bool,
+    pub underline: bool,
+}
+
+impl Style {
+    const fn new(color: Color) -> Style {
+        Style {
+            color: Some(color),
+            bold: false,
+            underline: false,
+        }
+    }
+
+    const fn bold(self) -> Style {
+        Style { bold: true, ..self }
+    }
+
+    const fn underline(self) -> Style {
+        Style {
+            underline: true,
+            ..self
+        }
+    }
+
+    pub fn paint(&self, text: &str) -> ColoredString {
+        let mut painted = text.normal();
+        if let Some(color) = self.color {
+            painted = painted.color(color);
+        }
+        if self.bold {
+            painted = painted.bold();
+        }
+        if self.underline {
+            painted = painted.underline();
+        }
+        painted
+    }
+}
+
+/// A style for every [`Outcome`], plus the background of word tiles whose
+/// letter is not in the word and the letters on every tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    pub win: Style,
+    pub too_high: Style,
+    pub too_low: Style,
+    pub miss: Style,
+    pub lose: Style,
+    pub error: Style,
+    pub hint: Style,
+    pub absent: Style,
+    pub tile_text: Style,
+}
+
+/// The Okabe-Ito colours used by [`Theme::COLORBLIND`].
+const BLUE: Color = rgb(0x00, 0x72, 0xB2);
+const ORANGE: Color = rgb(0xE6, 0x9F, 0x00);
+const SKY_BLUE: Color = rgb(0x56, 0xB4, 0xE9);
+const VERMILLION: Color = rgb(0xD5, 0x5E, 0x00);
+const REDDISH_PURPLE: Color = rgb(0xCC, 0x79, 0xA7);
+const GREY: Color = rgb(0x80, 0x80, 0x80);
+
+const fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::TrueColor { r, g, b }
+}
+
+impl Theme {
+    pub const DEFAULT: Theme = Theme {
+        win: Style::new(Color::Green),
+        too_high: Style::new(Color::Red),
+        too_low: Style::new(Color::Red),
+        miss: Style::new(Color::Red),
+        lose: Style::new(Color::Red),
+        error: Style::new(Color::Red),
+        hint: Style::new(Color::Yellow),
+        absent: Style::new(Color::BrightBlack),
+        tile_text: Style::new(Color::Black),
+    };
+
+    /// Never asks anyone to tell red from green: wins are blue, and too
+    /// high and too low differ in both colour and weight.
+    pub const COLORBLIND: Theme = Theme {
+        win: Style::new(BLUE).bold(),
+        too_high: Style::new(ORANGE).bold(),
+        too_low: Style::new(SKY_BLUE),
+        miss: Style::new(ORANGE),
+        lose: Style::new(VERMILLION).bold(),
+        error: Style::new(VERMILLION).underline(),
+        hint: Style::new(REDDISH_PURPLE),
+        absent: Style::new(GREY),
+        tile_text: Style::new(Color::Black),
+    };
+
+    pub const NAMES: [&'static str; 2] = ["default", "colorblind"];
+
+    pub fn named(name: &str) -> Option<Theme> {
+        match name {
+            "default" => Some(Theme::DEFAULT),
+            "colorblind" => Some(Theme::COLORBLIND),
+            _ => None,
+        }
+    }
+
+    /// Where a custom theme is picked up from when `--theme` is not given.
+    pub fn default_path() -> Option<PathBuf> {
+        storage::data_dir().map(|dir| dir.join("theme.json"))
+    }
+
+    /// Reads a theme file. Unlike the data files, a missing theme is an
+    /// error, since someone asked for it by name.
+    pub fn load(path: &Path) -> io::Result<Theme> {
+        serde_json::from_slice(&fs::read(path)?)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn style(&self, outcome: Outcome) -> Style {
+        match outcome {
+            Outcome::Win => self.win,
+            Outcome::TooHigh => self.too_high,
+            Outcome::TooLow => self.too_low,
+            Outcome::Miss => self.miss,
+            Outcome::Lose => self.lose,
+            Outcome::Error => self.error,
+            Outcome::Hint => self.hint,
+        }
+    }
+
+    pub fn paint(&self, outcome: Outcome, text: &str) -> ColoredString {
+        self.style(outcome).paint(text)
+    }
+
+    /// Paints a word tile: `text` in the `tile_text` style on a background
+    /// of the colour for `mark`, or reversed if that style has no colour.
+    pub fn tile(&self, mark: Mark, text: &str) -> ColoredString {
+        let background = match mark {
+            Mark::Correct => self.win,
+            Mark::Present => self.hint,
+            Mark::Absent => self.absent,
+        };
+        match background.color {
+            Some(color) => self.tile_text.paint(text).on_color(color),
+            None => text.reversed(),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::DEFAULT
+    }
+}
+
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Color>, D::Error> {
+    let Some(name) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    parse_color(&name)
+        .map(Some)
+        .ok_or_else(|| serde::de::Error::custom(format!("unknown color `{}`", name)))
+}
+
+fn parse_color(name: &str) -> Option<Color> {
+    match name.strip_prefix('#') {
+        Some(hex) if hex.len() == 6 => {
+            let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+            Some(rgb(channel(0)?, channel(2)?, channel(4)?))
+        }
+        Some(_) => None,
+        None => Color::from_str(name).ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        |name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn auto_colors_only_terminals() {
+        assert!(ColorChoice::Auto.resolve(true, env(&[])));
+        assert!(!ColorChoice::Auto.resolve(false, env(&[])));
+        assert!(!ColorChoice::Auto.resolve(true, env(&[("CLICOLOR", "0")])));
+    }
+
+    #[test]
+    fn environment_and_flag_override_detection() {
+        assert!(!ColorChoice::Auto.resolve(true, env(&[("NO_COLOR", "1")])));
+        assert!(ColorChoice::Auto.resolve(true, env(&[("NO_COLOR", "")])));
+        assert!(ColorChoice::Auto.resolve(false, env(&[("CLICOLOR_FORCE", "1")])));
+        assert!(!ColorChoice::Auto.resolve(false, env(&[("CLICOLOR_FORCE", "0")])));
+        assert!(
+            !ColorChoice::Auto.resolve(false, env(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")]))
+        );
+        assert!(ColorChoice::Always.resolve(false, env(&[("NO_COLOR", "1")])));
+        assert!(!ColorChoice::Never.resolve(true, env(&[("CLICOLOR_FORCE", "1")])));
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+
+    #[test]
+    fn theme_files_override_single_styles() {
+        let theme: Theme = serde_json::from_str(
+            r##"{ "win": { "color": "#0072b2", "bold": true }, "hint": { "color": "bright blue" } }"##,
+        )
+        .unwrap();
+        assert_eq!(theme.win, Style::new(BLUE).bold());
+        assert_eq!(theme.hint, Style::new(Color::BrightBlue));
+        assert_eq!(theme.lose, Theme::DEFAULT.lose);
+        assert_eq!(theme.absent, Theme::DEFAULT.absent);
+
+        let theme: Theme = serde_json::from_str(r#"{ "absent": { "color": "white" } }"#).unwrap();
+        assert_eq!(theme.absent, Style::new(Color::White));
+
+        assert!(serde_json::from_str::<Theme>(r##"{ "win": { "color": "#12" } }"##).is_err());
+        assert!(serde_json::from_str::<Theme>(r#"{ "wim": {} }"#).is_err());
+    }
+
+    #[test]
+    fn tiles_take_both_colours_from_the_theme() {
+        let tile = Theme::COLORBLIND.tile(Mark::Absent, " A ");
+        assert_eq!(tile.fgcolor, Some(Color::Black));
+        assert_eq!(tile.bgcolor, Some(GREY));
+
+        let theme: Theme =
+            serde_json::from_str(r#"{ "hint": {}, "tile_text": { "color": "white" } }"#).unwrap();
+        let tile = theme.tile(Mark::Correct, " A ");
+        assert_eq!(tile.fgcolor, Some(Color::White));
+        assert_eq!(tile.bgcolor, Some(Color::Green));
+        let tile = theme.tile(Mark::Present, " A ");
+        assert_eq!((tile.fgcolor, tile.bgcolor), (None, None));
+        assert_eq!(tile.style, colored::Style::default().reversed());
+    }
+
+    #[test]
+    fn colorblind_outcomes_are_told_apart_without_red_and_green() {
+        let theme = Theme::COLORBLIND;
+        let styles = [
+            Outcome::Win,
+            Outcome::TooHigh,
+            Outcome::TooLow,
+            Outcome::Miss,
+            Outcome::Lose,
+            Outcome::Hint,
+        ]
+        .map(|outcome| theme.style(outcome));
+        for (i, style) in styles.iter().enumerate() {
+            assert!(![Some(Color::Red), Some(Color::Green)].contains(&style.color));
+            assert!(styles[i + 1..].iter().all(|other| other != style));
+        }
+    }
+}
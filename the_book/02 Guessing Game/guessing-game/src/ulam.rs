@@ -0,0 +1,344 @@
+//! Ulam's game: the host may lie in up to `k` of its higher/lower answers.
+//!
+//! A number stays possible as long as explaining the answers with it as the
+//! secret takes at most `k` lies. The guesses cut the range into runs of
+//! numbers that every answer treats alike, so the bookkeeping is per run
+//! rather than per number and works for any range.
+
+use crate::game::{GuessError, GuessOutcome, Status, Turn, optimal_attempts};
+use crate::input::parse_guess;
+use crate::reverse::Answer;
+use rand::distributions::Uniform;
+use rand::prng::ChaChaRng;
+use rand::{Rng, SeedableRng};
+use std::ops::RangeInclusive;
+
+/// The most lies a game may allow.
+pub const MAX_LIES: u32 = 5;
+
+/// How often the host lies while it still has lies to tell.
+const LIE_CHANCE: f64 = 0.25;
+
+/// The attempt budget for a game with `lies` lies, enough for `Solver` to
+/// always win.
+pub fn attempts_needed(range: &RangeInclusive<u32>, lies: u32) -> u32 {
+    (lies + 1) * optimal_attempts(range) + 2 * lies
+}
+
+/// A run of numbers that the answers so far all treat alike.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidates {
+    pub numbers: RangeInclusive<u32>,
+    /// How many answers would have to be lies if the secret were here.
+    pub lies: u32,
+}
+
+impl Candidates {
+    pub fn count(&self) -> u64 {
+        u64::from(self.numbers.end() - self.numbers.start()) + 1
+    }
+}
+
+/// Keeps track of which numbers are still possible given the answers and
+/// the number of lies allowed.
+#[derive(Debug, Clone)]
+pub struct Tracker {
+    range: RangeInclusive<u32>,
+    lies: u32,
+    answers: Vec<Answer>,
+}
+
+impl Tracker {
+    pub fn new(range: RangeInclusive<u32>, lies: u32) -> Tracker {
+        assert!(!range.is_empty(), "the range must not be empty");
+        Tracker {
+            range,
+            lies,
+            answers: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, guess: u32, outcome: GuessOutcome) {
+        self.answers.push(Answer { guess, outcome });
+    }
+
+    pub fn answers(&self) -> &[Answer] {
+        &self.answers
+    }
+
+    /// How many answers would be lies if `secret` were the secret.
+    pub fn lies_needed(&self, secret: u32) -> u32 {
+        self.answers
+            .iter()
+            .filter(|answer| GuessOutcome::from(answer.guess.cmp(&secret)) != answer.outcome)
+            .count() as u32
+    }
+
+    /// Every number that could still be the secret, in order.
+    pub fn candidates(&self) -> Vec<Candidates> {
+        let mut guesses: Vec<u32> = self
+            .answers
+            .iter()
+            .map(|answer| answer.guess)
+            .filter(|guess| self.range.contains(guess))
+            .collect();
+        guesses.sort_unstable();
+        guesses.dedup();
+
+        let mut runs = Vec::new();
+        let mut next = Some(*self.range.start());
+        for guess in guesses {
+            if let Some(start) = next
+                && start < guess
+            {
+                runs.push(start..=guess - 1);
+            }
+            runs.push(guess..=guess);
+            next = guess.checked_add(1);
+        }
+        if let Some(start) = next
+            && start <= *self.range.end()
+        {
+            runs.push(start..=*self.range.end());
+        }
+
+        runs.into_iter()
+            .map(|numbers| Candidates {
+                lies: self.lies_needed(*numbers.start()),
+                numbers,
+            })
+            .filter(|run| run.lies <= self.lies)
+            .collect()
+    }
+
+    /// How many numbers could still be the secret.
+    pub fn remaining(&self) -> u64 {
+        self.candidates().iter().map(Candidates::count).sum()
+    }
+}
+
+/// The host of a lying game. It only ever lies about higher and lower, never
+/// about a correct guess, and never more than `lies` times.
+#[derive(Debug, Clone)]
+pub struct LyingHost {
+    secret: u32,
+    rng: ChaChaRng,
+    tracker: Tracker,
+    max_attempts: Option<u32>,
+    lies_told: u32,
+}
+
+impl LyingHost {
+    /// Starts a game whose secret and lies are fully determined by `seed`.
+    pub fn seeded(seed: u64, range: RangeInclusive<u32>, lies: u32) -> LyingHost {
+        let mut rng = ChaChaRng::seed_from_u64(seed);
+        let secret = rng.sample(Uniform::new_inclusive(*range.start(), *range.end()));
+        LyingHost {
+            secret,
+            rng,
+            tracker: Tracker::new(range, lies),
+            max_attempts: None,
+            lies_told: 0,
+        }
+    }
+
+    /// Limits the player to `max_attempts` guesses.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> LyingHost {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Answers a guess, perhaps with a lie.
+    pub fn guess(&mut self, guess: u32) -> Result<GuessOutcome, GuessError> {
+        if self.status() != Status::Playing {
+            return Err(GuessError::GameOver);
+        }
+        if !self.tracker.range.contains(&guess) {
+            return Err(GuessError::OutOfRange {
+                guess,
+                range: self.tracker.range.clone(),
+            });
+        }
+
+        let mut outcome = GuessOutcome::from(guess.cmp(&self.secret));
+        if outcome != GuessOutcome::Win
+            && self.lies_told < self.tracker.lies
+            && self.rng.gen_bool(LIE_CHANCE)
+        {
+            self.lies_told += 1;
+            outcome = match outcome {
+                GuessOutcome::TooSmall => GuessOutcome::TooBig,
+                _ => GuessOutcome::TooSmall,
+            };
+        }
+        self.tracker.record(guess, outcome);
+        Ok(outcome)
+    }
+
+    /// Parses a raw line of input and, if it holds a guess, makes it.
+    pub fn take_turn(&mut self, line: &str) -> Turn {
+        let guess = match parse_guess(line) {
+            Ok(guess) => guess,
+            Err(err) => return Turn::Invalid(err),
+        };
+        match self.guess(guess) {
+            Ok(outcome) => Turn::Guessed { guess, outcome },
+            Err(error) => Turn::Rejected { guess, error },
+        }
+    }
+
+    pub fn secret(&self) -> u32 {
+        self.secret
+    }
+
+    /// The number of lies the host is allowed to tell.
+    pub fn lies(&self) -> u32 {
+        self.tracker.lies
+    }
+
+    pub fn tracker(&self) -> &Tracker {
+        &self.tracker
+    }
+
+    /// The positions in `tracker().answers()` of every answer that was a lie.
+    pub fn lies_told(&self) -> Vec<usize> {
+        self.tracker
+            .answers()
+            .iter()
+            .enumerate()
+            .filter(|(_, answer)| {
+                GuessOutcome::from(answer.guess.cmp(&self.secret)) != answer.outcome
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.tracker.answers().len() as u32
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn status(&self) -> Status {
+        match self.tracker.answers().last() {
+            Some(answer) if answer.outcome == GuessOutcome::Win => Status::Won,
+            _ if self.attempts_left() == Some(0) => Status::Lost,
+            _ => Status::Playing,
+        }
+    }
+}
+
+/// Plays the lying game by always guessing the weighted median of the
+/// numbers still possible, where a number that needs fewer lies counts for
+/// more. Every answer either wins or costs one side of the median a lie, so
+/// it always gets there.
+#[derive(Debug, Clone)]
+pub struct Solver {
+    tracker: Tracker,
+}
+
+impl Solver {
+    pub fn new(range: RangeInclusive<u32>, lies: u32) -> Solver {
+        Solver {
+            tracker: Tracker::new(range, lies),
+        }
+    }
+
+    pub fn next_guess(&self) -> u32 {
+        let candidates = self.tracker.candidates();
+        let weight = |run: &Candidates| 1u128 << (self.tracker.lies - run.lies);
+        let total: u128 = candidates
+            .iter()
+            .map(|run| u128::from(run.count()) * weight(run))
+            .sum();
+
+        let mut before = 0;
+        for run in &candidates {
+            let run_total = u128::from(run.count()) * weight(run);
+            if 2 * (before + run_total) > total {
+                let offset = (total / 2).saturating_sub(before) / weight(run);
+                return run.numbers.start() + offset as u32;
+            }
+            before += run_total;
+        }
+        *self.tracker.range.start()
+    }
+
+    pub fn answer(&mut self, guess: u32, outcome: GuessOutcome) {
+        self.tracker.record(guess, outcome);
+    }
+}
+
+/// Lets `Solver` play out a seeded game and returns the host once it ends.
+pub fn solve(seed: u64, range: RangeInclusive<u32>, lies: u32) -> LyingHost {
+    let budget = attempts_needed(&range, lies);
+    let mut host = LyingHost::seeded(seed, range.clone(), lies).with_max_attempts(budget);
+    let mut solver = Solver::new(range, lies);
+    while host.status() == Status::Playing {
+        let guess = solver.next_guess();
+        let outcome = host.guess(guess).expect("the solver guesses within range");
+        solver.answer(guess, outcome);
+    }
+    host
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_are_the_numbers_within_the_lie_budget() {
+        let mut tracker = Tracker::new(1..=10, 1);
+        tracker.record(5, GuessOutcome::TooSmall);
+        tracker.record(8, GuessOutcome::TooBig);
+        tracker.record(3, GuessOutcome::TooBig);
+        let candidates: Vec<_> = tracker
+            .candidates()
+            .into_iter()
+            .map(|run| (run.numbers, run.lies))
+            .collect();
+        assert_eq!(candidates, vec![(1..=2, 1), (6..=7, 1)]);
+        assert_eq!(tracker.remaining(), 4);
+        assert_eq!(tracker.lies_needed(8), 2);
+    }
+
+    #[test]
+    fn the_host_lies_at_most_k_times_and_never_about_a_win() {
+        for seed in 0..200 {
+            let host = solve(seed, 1..=100, 2);
+            assert!(host.lies_told().len() <= 2);
+            assert_eq!(
+                host.tracker().lies_needed(host.secret()),
+                host.lies_told().len() as u32
+            );
+        }
+    }
+
+    #[test]
+    fn the_solver_always_wins_within_the_budget() {
+        for lies in 0..=3 {
+            for seed in 0..200 {
+                let host = solve(seed, 1..=1000, lies);
+                assert_eq!(
+                    host.status(),
+                    Status::Won,
+                    "seed {} with {} lies",
+                    seed,
+                    lies
+                );
+            }
+        }
+        let host = solve(7, 0..=u32::MAX, MAX_LIES);
+        assert_eq!(host.status(), Status::Won);
+    }
+
+    #[test]
+    fn without_lies_the_solver_bisects() {
+        let host = solve(3, 1..=100, 0);
+        assert!(host.attempts() <= optimal_attempts(&(1..=100)));
+        assert!(host.lies_told().is_empty());
+    }
+}
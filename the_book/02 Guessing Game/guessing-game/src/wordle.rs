@@ -0,0 +1,359 @@
+//! Word mode: guess a hidden five-letter word. Each letter of a guess is
+//! marked as in the right spot, elsewhere in the word, or absent.
+
+use crate::game::Status;
+use crate::i18n::{Lang, Localize};
+use rand::prng::ChaChaRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+/// The bundled dictionary, one lowercase word per line in sorted order.
+/// Secrets are drawn from it and every guess must be in it.
+const WORDS: &str = include_str!("words.txt");
+
+/// The length of every word in the game.
+pub const LENGTH: usize = 5;
+
+/// The attempt budget unless `--attempts` says otherwise.
+pub const ATTEMPTS: u32 = 6;
+
+pub fn words() -> impl Iterator<Item = &'static str> {
+    WORDS.lines()
+}
+
+pub fn is_word(word: &str) -> bool {
+    words().any(|known| known == word)
+}
+
+/// How one letter of a guess compares with the secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    /// The right letter in the right spot.
+    Correct,
+    /// The letter appears elsewhere in the word.
+    Present,
+    Absent,
+}
+
+impl fmt::Display for Mark {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Mark::Correct => "🟩",
+            Mark::Present => "🟨",
+            Mark::Absent => "⬜",
+        })
+    }
+}
+
+/// Marks each letter of `guess` against `secret`. A letter that appears in
+/// the guess more often than in the secret is only marked present as many
+/// times as the secret has it, exact matches first.
+///
+/// # Panics
+///
+/// If either word is shorter than [`LENGTH`] or holds anything but the
+/// lowercase letters a to z. [`WordGame`] only calls it with words from the
+/// word list, which never do.
+pub(crate) fn feedback(secret: &str, guess: &str) -> [Mark; LENGTH] {
+    let secret = secret.as_bytes();
+    let guess = guess.as_bytes();
+    let mut marks = [Mark::Absent; LENGTH];
+    let mut unmatched = [0u8; 26];
+
+    for i in 0..LENGTH {
+        if guess[i] == secret[i] {
+            marks[i] = Mark::Correct;
+        } else {
+            unmatched[usize::from(secret[i] - b'a')] += 1;
+        }
+    }
+    for i in 0..LENGTH {
+        let left = &mut unmatched[usize::from(guess[i] - b'a')];
+        if marks[i] != Mark::Correct && *left > 0 {
+            *left -= 1;
+            marks[i] = Mark::Present;
+        }
+    }
+    marks
+}
+
+/// Why a guess was not accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordError {
+    Empty,
+    WrongLength(usize),
+    NotALetter(char),
+    NotAWord(String),
+    /// Hard mode: a letter found in the right spot was moved or dropped.
+    Moved {
+        letter: char,
+        position: usize,
+    },
+    /// Hard mode: a letter known to be in the word is missing.
+    Missing(char),
+    GameOver,
+}
+
+impl Localize for WordError {
+    fn localize(&self, lang: Lang) -> String {
+        match self {
+            WordError::Empty => lang.format("empty_word", &[]),
+            WordError::WrongLength(len) => {
+                lang.format("word_length", &[("length", &LENGTH), ("got", len)])
+            }
+            WordError::NotALetter(c) => lang.format("not_a_letter", &[("letter", c)]),
+            WordError::NotAWord(word) => lang.format("not_a_word", &[("word", word)]),
+            WordError::Moved { letter, position } => lang.format(
+                "letter_moved",
+                &[
+                    ("position", &(position + 1)),
+                    ("letter", &letter.to_ascii_uppercase()),
+                ],
+            ),
+            WordError::Missing(letter) => lang.format(
+                "letter_missing",
+                &[("letter", &letter.to_ascii_uppercase())],
+            ),
+            WordError::GameOver => lang.format("game_over", &[]),
+        }
+    }
+}
+
+impl fmt::Display for WordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.localize(Lang::En))
+    }
+}
+
+impl std::error::Error for WordError {}
+
+/// Turns a line of input into a lowercase word from the dictionary.
+pub fn parse_word(line: &str) -> Result<String, WordError> {
+    let word = line.trim().to_ascii_lowercase();
+    if word.is_empty() {
+        return Err(WordError::Empty);
+    }
+    if let Some(c) = word.chars().find(|c| !c.is_ascii_lowercase()) {
+        return Err(WordError::NotALetter(c));
+    }
+    if word.len() != LENGTH {
+        return Err(WordError::WrongLength(word.len()));
+    }
+    if !is_word(&word) {
+        return Err(WordError::NotAWord(word));
+    }
+    Ok(word)
+}
+
+/// What one line of player input did to a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordTurn {
+    Invalid(WordError),
+    Marked {
+        guess: String,
+        marks: [Mark; LENGTH],
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct WordGame {
+    secret: String,
+    hard: bool,
+    max_attempts: Option<u32>,
+    history: Vec<(String, [Mark; LENGTH])>,
+}
+
+impl WordGame {
+    /// Starts a game with a known secret.
+    ///
+    /// Panics if `secret` is not in the dictionary.
+    pub fn new(secret: &str) -> WordGame {
+        assert!(is_word(secret), "the secret must be in the word list");
+        WordGame {
+            secret: secret.to_string(),
+            hard: false,
+            max_attempts: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// Starts a game whose secret is fully determined by `seed`.
+    pub fn seeded(seed: u64) -> WordGame {
+        let mut rng = ChaChaRng::seed_from_u64(seed);
+        let index = rng.gen_range(0, words().count());
+        WordGame::new(words().nth(index).expect("index is within the word list"))
+    }
+
+    /// Makes every guess keep the letters revealed so far.
+    pub fn with_hard_mode(mut self) -> WordGame {
+        self.hard = true;
+        self
+    }
+
+    /// Limits the game to `max_attempts` guesses, after which it is lost.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> WordGame {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Marks a guess and records it. Words outside the dictionary and, in
+    /// hard mode, guesses that ignore earlier hints are not recorded.
+    pub fn guess(&mut self, guess: &str) -> Result<[Mark; LENGTH], WordError> {
+        if self.status() != Status::Playing {
+            return Err(WordError::GameOver);
+        }
+        if !is_word(guess) {
+            return Err(WordError::NotAWord(guess.to_string()));
+        }
+        if self.hard {
+            self.check_hard_mode(guess)?;
+        }
+        let marks = feedback(&self.secret, guess);
+        self.history.push((guess.to_string(), marks));
+        Ok(marks)
+    }
+
+    /// Checks that `guess` keeps every green letter in place and uses every
+    /// yellow one, as many times as earlier guesses revealed it.
+    fn check_hard_mode(&self, guess: &str) -> Result<(), WordError> {
+        let guess = guess.as_bytes();
+        for (previous, marks) in &self.history {
+            let previous = previous.as_bytes();
+            for (position, mark) in marks.iter().enumerate() {
+                if *mark == Mark::Correct && guess[position] != previous[position] {
+                    return Err(WordError::Moved {
+                        letter: char::from(previous[position]),
+                        position,
+                    });
+                }
+            }
+            for (position, mark) in marks.iter().enumerate() {
+                let letter = previous[position];
+                let revealed = (0..LENGTH)
+                    .filter(|&i| previous[i] == letter && marks[i] != Mark::Absent)
+                    .count();
+                let used = guess.iter().filter(|&&c| c == letter).count();
+                if *mark != Mark::Absent && used < revealed {
+                    return Err(WordError::Missing(char::from(letter)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a raw line of input and, if it holds a word, guesses it.
+    pub fn take_turn(&mut self, line: &str) -> WordTurn {
+        let guess = match parse_word(line) {
+            Ok(guess) => guess,
+            Err(err) => return WordTurn::Invalid(err),
+        };
+        match self.guess(&guess) {
+            Ok(marks) => WordTurn::Marked { guess, marks },
+            Err(err) => WordTurn::Invalid(err),
+        }
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn is_hard(&self) -> bool {
+        self.hard
+    }
+
+    pub fn history(&self) -> &[(String, [Mark; LENGTH])] {
+        &self.history
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn status(&self) -> Status {
+        match self.history.last() {
+            Some((guess, _)) if *guess == self.secret => Status::Won,
+            _ if self.attempts_left() == Some(0) => Status::Lost,
+            _ => Status::Playing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Mark::{Absent as A, Correct as C, Present as P};
+
+    #[test]
+    fn the_word_list_is_sorted_lowercase_five_letter_words() {
+        let words: Vec<_> = words().collect();
+        assert!(words.len() > 500);
+        assert!(words.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(
+            words
+                .iter()
+                .all(|w| w.len() == LENGTH && w.bytes().all(|b| b.is_ascii_lowercase()))
+        );
+    }
+
+    #[test]
+    fn repeated_letters_are_only_marked_as_often_as_they_occur() {
+        assert_eq!(feedback("crane", "crane"), [C; 5]);
+        assert_eq!(feedback("abbey", "kebab"), [A, P, C, P, P]);
+        assert_eq!(feedback("other", "otter"), [C, C, A, C, C]);
+        // The exact match claims the only `e` before the first one can.
+        assert_eq!(feedback("lemon", "geese"), [A, C, A, A, A]);
+        assert_eq!(feedback("speed", "eerie"), [P, P, A, A, A]);
+    }
+
+    #[test]
+    fn guesses_must_be_dictionary_words() {
+        assert_eq!(parse_word(" Crane\n"), Ok("crane".to_string()));
+        assert_eq!(parse_word("\n"), Err(WordError::Empty));
+        assert_eq!(parse_word("cran"), Err(WordError::WrongLength(4)));
+        assert_eq!(parse_word("cr4ne"), Err(WordError::NotALetter('4')));
+        assert_eq!(
+            parse_word("zzzzz"),
+            Err(WordError::NotAWord("zzzzz".to_string()))
+        );
+    }
+
+    #[test]
+    fn hard_mode_keeps_revealed_letters() {
+        let mut game = WordGame::new("crane").with_hard_mode();
+        assert_eq!(game.guess("trace"), Ok([A, C, C, P, C]));
+        assert_eq!(
+            game.guess("drive"),
+            Err(WordError::Moved {
+                letter: 'a',
+                position: 2
+            })
+        );
+        assert_eq!(game.guess("brave"), Err(WordError::Missing('c')));
+        assert_eq!(game.attempts(), 1);
+        assert!(game.guess("crate").is_ok());
+
+        let mut easy = WordGame::new("crane");
+        easy.guess("trace").unwrap();
+        assert!(easy.guess("drive").is_ok());
+    }
+
+    #[test]
+    fn games_end_on_the_secret_or_the_budget() {
+        let mut game = WordGame::seeded(9).with_max_attempts(2);
+        let secret = game.secret().to_string();
+        let wrong = words().find(|w| *w != secret).unwrap();
+        assert!(matches!(game.take_turn(wrong), WordTurn::Marked { .. }));
+        assert_eq!(game.status(), Status::Playing);
+        game.take_turn(&secret);
+        assert_eq!(game.status(), Status::Won);
+        assert_eq!(
+            game.take_turn(wrong),
+            WordTurn::Invalid(WordError::GameOver)
+        );
+    }
+}
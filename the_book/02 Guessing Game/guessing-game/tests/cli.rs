@@ -0,0 +1,827 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+/// A fresh data directory so tests never touch the real leaderboard.
+fn data_dir(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Runs the game with a shared data directory, for tests that do not look
+/// at the leaderboard.
+fn run(args: &[&str], stdin: &str) -> Output {
+    run_in(
+        &Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli"),
+        args,
+        stdin,
+    )
+}
+
+fn run_in(data: &Path, args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_guessing-game"))
+        .args(["--name", "tester"])
+        .args(args)
+        .env("NO_COLOR", "1")
+        .env("LC_ALL", "C")
+        .env("XDG_DATA_HOME", data)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start the game");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn eof_exits_with_distinct_status() {
+    let output = run(&[], "");
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn bad_input_is_reported_before_eof() {
+    let output = run(&[], "\nabc\n-4\n99999999999\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Please type a number."));
+    assert!(stdout.contains("`abc` is not a number."));
+    assert!(stdout.contains("`-4` is negative"));
+    assert!(stdout.contains("`99999999999` is too large"));
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn invalid_arguments_exit_with_usage_status() {
+    let output = run(&["--min", "9", "--max", "1"], "");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn same_seed_and_guesses_give_identical_output() {
+    let guesses = "50\n25\n75\n12\n88\n";
+    let first = run(&["--seed", "42"], guesses);
+    let second = run(&["--seed", "42"], guesses);
+    assert_eq!(first.stdout, second.stdout);
+    assert_eq!(first.stderr, second.stderr);
+    assert_eq!(first.status.code(), second.status.code());
+}
+
+#[test]
+fn secret_is_only_printed_when_revealed() {
+    let hidden = String::from_utf8(run(&["--seed", "1"], "").stdout).unwrap();
+    assert!(!hidden.contains("The secret number is"));
+
+    let revealed = String::from_utf8(run(&["--seed", "1", "--reveal"], "").stdout).unwrap();
+    assert!(revealed.contains("The secret number is"));
+}
+
+/// Plays an easy game with the given color environment and flags.
+fn colored_output(env: &[(&str, &str)], args: &[&str], stdin: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_guessing-game"))
+        .args(["--name", "tester", "-d", "easy", "--seed", "1", "--reveal"])
+        .args(args)
+        .env_remove("NO_COLOR")
+        .env_remove("CLICOLOR_FORCE")
+        .envs(env.iter().copied())
+        .env("LC_ALL", "C")
+        .env("XDG_DATA_HOME", data_dir("color"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    String::from_utf8(child.wait_with_output().unwrap().stdout).unwrap()
+}
+
+#[test]
+fn color_follows_the_terminal_environment_and_flag() {
+    let colored =
+        |env: &[(&str, &str)], args: &[&str]| colored_output(env, args, "oops\n").contains('\x1b');
+    assert!(!colored(&[], &[]));
+    assert!(colored(&[("CLICOLOR_FORCE", "1")], &[]));
+    assert!(!colored(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")], &[]));
+    assert!(colored(&[("NO_COLOR", "1")], &["--color=always"]));
+    assert!(!colored(&[("CLICOLOR_FORCE", "1")], &["--color", "never"]));
+
+    let secret: String = colored_output(&[], &[], "")
+        .lines()
+        .find_map(|line| line.strip_prefix("The secret number is: "))
+        .unwrap()
+        .into();
+    let wrong = if secret == "1" { "2" } else { "1" };
+    let themed = colored_output(
+        &[("COLORTERM", "truecolor")],
+        &["--color=always", "--theme", "colorblind"],
+        &format!("{}\n{}\n", wrong, secret),
+    );
+    // Okabe-Ito blue for the win, never plain green.
+    assert!(themed.contains("38;2;0;114;178"), "{:?}", themed);
+    assert!(!themed.contains("\x1b[32m"), "{:?}", themed);
+
+    let missing = run(&["--theme", "no-such-theme.json"], "");
+    assert!(!missing.status.success());
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("colorblind"));
+}
+
+#[test]
+fn messages_and_numbers_follow_the_language() {
+    let spanish = String::from_utf8(run(&["--lang", "es", "-d", "hard"], "").stdout).unwrap();
+    assert!(spanish.contains("¡Adivina el número!"), "{}", spanish);
+    assert!(spanish.contains("entre 1 y 10.000"), "{}", spanish);
+
+    let hindi = String::from_utf8(run(&["--lang", "hi", "-d", "insane"], "").stdout).unwrap();
+    assert!(hindi.contains("4,29,49,67,295"), "{}", hindi);
+
+    let english = String::from_utf8(run(&["-d", "hard"], "").stdout).unwrap();
+    assert!(english.contains("between 1 and 10,000"), "{}", english);
+}
+
+#[test]
+fn special_modes_and_errors_follow_the_language() {
+    let reverse = run(&["--lang", "es", "--reverse"], "sí\nhigher\n");
+    let stdout = String::from_utf8(reverse.stdout).unwrap();
+    assert!(stdout.contains("¿Es el 50?"), "{}", stdout);
+    assert!(
+        stdout.contains("Responde higher, lower o correct."),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("¿Es el 75?"), "{}", stdout);
+    let stderr = String::from_utf8(reverse.stderr).unwrap();
+    assert!(stderr.contains("la entrada se cerró"), "{}", stderr);
+
+    let code = run(&["--lang", "hi", "--code", "--seed", "1"], "12\n");
+    let stdout = String::from_utf8(code.stdout).unwrap();
+    assert!(stdout.contains("कोड तोड़िए"), "{}", stdout);
+    assert!(stdout.contains("कोड में 4 अंक हैं, 2 नहीं।"), "{}", stdout);
+}
+
+#[test]
+fn argument_errors_and_help_follow_the_language() {
+    let output = run(&["--players", "9", "--lang=es"], "");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("necesitan de 2 a 8 jugadores, no 9"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("Uso: guessing-game"), "{}", stderr);
+
+    let help = run(&["--lang", "hi", "--help"], "");
+    let stdout = String::from_utf8(help.stdout).unwrap();
+    assert!(stdout.starts_with("उपयोग: guessing-game"), "{}", stdout);
+}
+
+#[test]
+fn hints_are_offered_and_cost_points() {
+    let output = run(
+        &["--seed", "3", "--hints", "--reveal"],
+        "hint parity\nhint parity\n1\n100\nhint nonsense\n",
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Hints on offer:"), "{}", stdout);
+    assert!(stdout.contains("The number is"), "{}", stdout);
+    assert!(stdout.contains("no parity hint to give"), "{}", stdout);
+    assert!(
+        stdout.contains("Warmer") || stdout.contains("Colder") || stdout.contains("same"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("nonsense"), "{}", stdout);
+}
+
+#[test]
+fn insight_shows_the_interval_and_bits_gained() {
+    let output = run(&["--seed", "5", "--insight"], "50\n25\n63\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("Still possible: 51 to 100 (50 numbers).\nThis guess gained 1.00 bits"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("gained 0.00 bits"), "{}", stdout);
+    assert!(stdout.contains("Overall efficiency: "), "{}", stdout);
+
+    let plain = String::from_utf8(run(&["--seed", "5"], "50\n").stdout).unwrap();
+    assert!(!plain.contains("bits"), "{}", plain);
+}
+
+#[test]
+fn running_out_of_attempts_loses() {
+    let output = run(
+        &[
+            "--seed",
+            "7",
+            "--min",
+            "1",
+            "--max",
+            "1000",
+            "--attempts",
+            "2",
+        ],
+        "1\n1000\n",
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("1 try left."), "{}", stdout);
+    assert!(stdout.contains("You lose, the number was"), "{}", stdout);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn finished_games_show_up_in_stats() {
+    let data = data_dir("stats");
+    let empty = run_in(&data, &["--stats"], "");
+    assert_eq!(
+        String::from_utf8(empty.stdout).unwrap(),
+        "No games recorded yet.\n"
+    );
+
+    run_in(
+        &data,
+        &["-d", "easy", "--attempts", "1", "--seed", "3"],
+        "1\n",
+    );
+    run_in(&data, &["-d", "easy", "--attempts", "1", "--seed", "3"], "");
+
+    let stats = String::from_utf8(run_in(&data, &["--stats"], "").stdout).unwrap();
+    let row = stats
+        .lines()
+        .find(|line| line.starts_with("tester"))
+        .unwrap();
+    let columns: Vec<&str> = row.split_whitespace().collect();
+    // The game that hit EOF is not counted.
+    assert_eq!(columns[..3], ["tester", "easy", "1"]);
+}
+
+#[test]
+fn daily_puzzle_can_only_be_played_once() {
+    let data = data_dir("daily");
+    let first = run_in(&data, &["--daily"], &"1\n".repeat(13));
+    let stdout = String::from_utf8(first.stdout).unwrap();
+    assert!(stdout.contains("Daily challenge #"), "{}", stdout);
+    let share = stdout
+        .lines()
+        .find(|line| line.starts_with("Guessing Game #"))
+        .unwrap();
+    assert!(!share.contains(" 1 "), "{}", share);
+
+    let second = run_in(&data, &["--daily"], "1\n");
+    let stdout = String::from_utf8(second.stdout).unwrap();
+    assert!(
+        stdout.contains("You have already played puzzle #"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains(share), "{}", stdout);
+    assert!(!stdout.contains("You guessed"), "{}", stdout);
+
+    let stats = String::from_utf8(run_in(&data, &["--stats"], "").stdout).unwrap();
+    assert!(stats.contains("daily"), "{}", stats);
+}
+
+#[test]
+fn daily_puzzles_can_be_recorded_and_replayed() {
+    let data = data_dir("daily-record");
+    std::fs::create_dir_all(&data).unwrap();
+    let log = data.join("daily.jsonl");
+    let log_arg = log.to_str().unwrap();
+    let played = run_in(&data, &["--daily", "--record", log_arg], "1\n2\n");
+    assert_eq!(played.status.code(), Some(3));
+
+    let replayed = run(&["replay", "--speed", "0", log_arg], "");
+    let stdout = String::from_utf8(replayed.stdout).unwrap();
+    assert!(stdout.contains("> 2"), "{}", stdout);
+    assert!(replayed.status.success(), "{}", stdout);
+
+    let evil = run(&["--evil", "--record", log_arg], "");
+    assert_eq!(evil.status.code(), Some(2));
+}
+
+#[test]
+fn speedruns_are_timed_and_keep_a_personal_best() {
+    let data = data_dir("speedrun");
+    let args = ["--speedrun", "-d", "easy", "--seed", "3"];
+    let first = String::from_utf8(run_in(&data, &args, "5\n8\n7\n").stdout).unwrap();
+    assert!(first.contains("Speedrun score: "), "{}", first);
+    assert!(first.contains("Guess times: "), "{}", first);
+    assert!(first.contains("New personal best!"), "{}", first);
+
+    let second = String::from_utf8(run_in(&data, &args, "1\n2\n3\n4\n6\n").stdout).unwrap();
+    assert!(second.contains("Personal best on easy: "), "{}", second);
+
+    let stats = String::from_utf8(run_in(&data, &["--stats"], "").stdout).unwrap();
+    assert!(stats.contains("Speedrunner"), "{}", stats);
+}
+
+#[test]
+fn time_limits_end_the_game_without_input() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_guessing-game"))
+        .args(["--name", "tester", "--time-limit", "1"])
+        .env("NO_COLOR", "1")
+        .env("LC_ALL", "C")
+        .env("XDG_DATA_HOME", data_dir("time-limit"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start the game");
+    // Keep stdin open so only the clock can end the game.
+    let stdin = child.stdin.take();
+    let output = child.wait_with_output().unwrap();
+    drop(stdin);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Time's up!"), "{}", stdout);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn ratings_follow_solo_and_hot_seat_games() {
+    let data = data_dir("ratings");
+    let solo =
+        String::from_utf8(run_in(&data, &["-d", "easy", "--seed", "3"], "7\n").stdout).unwrap();
+    assert!(solo.contains("Rating: 12"), "{}", solo);
+
+    let hotseat = run_in(
+        &data,
+        &["--players", "2", "-d", "easy", "--seed", "3"],
+        "ana\nbo\n5\n7\n",
+    );
+    let stdout = String::from_utf8(hotseat.stdout).unwrap();
+    assert!(
+        stdout.contains("ana              rating 1184 (-16)"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("bo               rating 1216 (+16)"),
+        "{}",
+        stdout
+    );
+
+    let ratings = String::from_utf8(run_in(&data, &["--ratings"], "").stdout).unwrap();
+    assert!(ratings.contains("tester"), "{}", ratings);
+    assert!(ratings.contains("┤•"), "{}", ratings);
+    let one = String::from_utf8(run_in(&data, &["--ratings", "--name", "bo"], "").stdout).unwrap();
+    assert!(one.contains("bo ") && !one.contains("ana"), "{}", one);
+}
+
+#[test]
+fn achievements_unlock_once_and_show_in_stats() {
+    let data = data_dir("achievements");
+    let args = ["-d", "easy", "--seed", "3"];
+    let first = String::from_utf8(run_in(&data, &args, "7\n").stdout).unwrap();
+    assert!(
+        first.contains("🏆 tester unlocked Mind Reader"),
+        "{}",
+        first
+    );
+
+    let second = String::from_utf8(run_in(&data, &args, "7\n").stdout).unwrap();
+    assert!(!second.contains("🏆"), "{}", second);
+
+    let stats = String::from_utf8(run_in(&data, &["--stats"], "").stdout).unwrap();
+    assert!(stats.contains("Mind Reader"), "{}", stats);
+}
+
+#[test]
+fn saved_games_resume_and_reject_tampering() {
+    let data = data_dir("save");
+    std::fs::create_dir_all(&data).unwrap();
+    let file = data.join("game.save");
+    let save = format!(":save {}\n", file.display());
+
+    let output = run_in(&data, &["--seed", "5"], &format!("50\n{}", save));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("Game saved to"), "{}", stdout);
+
+    let resume = ["--resume", file.to_str().unwrap()];
+    let output = run_in(&data, &resume, &format!("25\n{}", save));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Welcome back, tester!"), "{}", stdout);
+    assert!(stdout.contains("You guessed 50: too small."), "{}", stdout);
+    assert!(stdout.contains("8 tries left."), "{}", stdout);
+
+    let json = std::fs::read_to_string(&file).unwrap();
+    std::fs::write(
+        &file,
+        json.replace("\"max_attempts\": 10", "\"max_attempts\": 99"),
+    )
+    .unwrap();
+    let output = run_in(&data, &resume, "");
+    assert_eq!(output.status.code(), Some(65));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("has been modified"), "{}", stderr);
+}
+
+#[test]
+fn name_is_prompted_when_not_given() {
+    let data = data_dir("prompt");
+    let output = Command::new(env!("CARGO_BIN_EXE_guessing-game"))
+        .arg("--stats")
+        .env("XDG_DATA_HOME", &data)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_guessing-game"))
+        .args(["-d", "easy", "--attempts", "1"])
+        .env("NO_COLOR", "1")
+        .env("LC_ALL", "C")
+        .env("XDG_DATA_HOME", &data)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"ana\n0\n5\n")
+        .unwrap();
+    let stdout = String::from_utf8(child.wait_with_output().unwrap().stdout).unwrap();
+    assert!(stdout.contains("What's your name?"));
+
+    let stats = String::from_utf8(run_in(&data, &["--stats"], "").stdout).unwrap();
+    assert!(
+        stats.lines().any(|line| line.starts_with("ana")),
+        "{}",
+        stats
+    );
+}
+
+#[test]
+fn game_modes_are_mutually_exclusive() {
+    let output = run(&["--daily", "--players", "2"], "");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("--daily cannot be combined with --players"),
+        "{}",
+        stderr
+    );
+
+    let modes: [&[&str]; 12] = [
+        &["--speedrun"],
+        &["--daily"],
+        &["--players", "3"],
+        &["--reverse"],
+        &["--evil"],
+        &["--lies", "1"],
+        &["--code"],
+        &["--word"],
+        &["--resume", "f"],
+        &["--serve", "127.0.0.1:0"],
+        &["--connect", "127.0.0.1:7878"],
+        &["--bench", "100"],
+    ];
+    for (i, first) in modes.iter().enumerate() {
+        for second in &modes[i + 1..] {
+            let output = run(&[*first, *second].concat(), "");
+            assert_eq!(output.status.code(), Some(2), "{:?} {:?}", first, second);
+            let stderr = String::from_utf8(output.stderr).unwrap();
+            let message = format!("{} cannot be combined with {}", first[0], second[0]);
+            assert!(stderr.contains(&message), "{}", stderr);
+        }
+    }
+}
+
+#[test]
+fn reverse_mode_finds_the_number() {
+    let output = run(&["--reverse"], "lower\nhigher\nhigher\ncorrect\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Is it 50?"), "{}", stdout);
+    assert!(stdout.contains("Is it 25?"), "{}", stdout);
+    assert!(stdout.contains("Is it 37?"), "{}", stdout);
+    assert!(
+        stdout.contains("Your number is 43, found in 4 guesses."),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn evil_host_forces_the_worst_case() {
+    let output = run(&["--evil", "-d", "easy"], "1\n2\n3\n4\n5\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("1 try left, 6 numbers still possible."),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("You lose, the number was 6."), "{}", stdout);
+
+    let output = run(&["--evil", "-d", "easy"], "5\n8\n10\n9\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("You win! You cornered me in 4 guesses."),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn lying_host_owns_up_at_the_end() {
+    let guesses: String = (1..=16).map(|n| format!("{}\n", n)).collect();
+    let output = run(&["--lies", "1", "--seed", "4"], &guesses);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("I may lie in 1 of my answers."),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("You have 16 attempts."), "{}", stdout);
+    assert!(stdout.contains("My answers were:"), "{}", stdout);
+    assert!(stdout.contains("(a lie)"), "{}", stdout);
+
+    let output = run(&["--lies", "9"], "");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn code_mode_scores_bulls_and_cows() {
+    let output = run(
+        &["--code", "-d", "hard", "--seed", "1"],
+        "0123\n1123\nhint\n0278\n",
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("1 bull, 1 cow. 9 tries left."),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("1 appears more than once"), "{}", stdout);
+    assert!(
+        stdout.contains("720 codes are still possible."),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("You win! Cracked in 2 guesses."),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("The solver cracks this code in"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn word_mode_marks_letters_and_enforces_hard_mode() {
+    let output = run(
+        &["--word", "--seed", "1", "--hard-mode"],
+        "crane\nzzzzz\nslate\nprove\n",
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(" C  R  A  N  E  ⬜🟩⬜⬜🟩"), "{}", stdout);
+    assert!(
+        stdout.contains("`zzzzz` is not in the word list."),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Hard mode: letter 2 must be R."),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("You win! Found in 2 guesses."),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn reverse_mode_catches_lies() {
+    let output = run(
+        &["--reverse", "-d", "easy"],
+        "higher\nsideways\nlower\nlower\n",
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("Please answer higher, lower or correct."),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("answer #3 (lower than 6) contradicts answer #1 (higher than 5)"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn bench_prints_csv_for_each_strategy() {
+    let output = run(&["--bench", "50", "--seed", "3", "--csv"], "");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("strategy,games,mean,median,worst"));
+    let strategies: Vec<&str> = lines.map(|line| line.split(',').next().unwrap()).collect();
+    assert_eq!(strategies, ["linear", "random", "bisection", "biased"]);
+}
+
+#[test]
+fn batch_mode_prints_a_json_report_per_game() {
+    let games = concat!(
+        r#"{"seed": 3, "min": 1, "max": 10, "max_attempts": 2, "guesses": [1, "ten", 10]}"#,
+        "\n",
+        r#"{"seed": 3, "min": 1, "max": 10, "max_attempts": null, "guesses": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]}"#,
+        "\n",
+    );
+    let output = run(&["batch"], games);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let reports: Vec<&str> = stdout.lines().collect();
+    assert_eq!(reports.len(), 2, "{}", stdout);
+    assert!(
+        reports[0].starts_with(r#"{"game":1,"seed":3,"#),
+        "{}",
+        stdout
+    );
+    assert!(
+        reports[0].contains(r#""status":"lost","attempts":2"#),
+        "{}",
+        stdout
+    );
+    assert!(reports[0].contains(r#"{"invalid":"#), "{}", stdout);
+    assert!(reports[1].contains(r#""status":"won""#), "{}", stdout);
+    assert!(!stdout.contains('\x1b'));
+
+    let data = data_dir("batch");
+    std::fs::create_dir_all(&data).unwrap();
+    let file = data.join("games.jsonl");
+    std::fs::write(&file, format!("{}{{\"seed\": 1}}\n", games)).unwrap();
+    let output = run(&["batch", file.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(65));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 3);
+    assert!(
+        stdout.contains(r#"{"game":3,"error":"missing field"#),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn recorded_sessions_replay_and_verify() {
+    let data = data_dir("replay");
+    std::fs::create_dir_all(&data).unwrap();
+    let log = data.join("session.jsonl");
+    let log_arg = log.to_str().unwrap();
+
+    let played = run(
+        &["-d", "easy", "--seed", "3", "--record", log_arg],
+        "5\nfive\n3\n8\n2\n9\n1\n",
+    );
+    assert!(played.status.success());
+
+    let replayed = run(&["replay", "--speed", "0", log_arg], "");
+    let stdout = String::from_utf8(replayed.stdout).unwrap();
+    assert!(stdout.contains("> five"), "{}", stdout);
+    assert!(
+        stdout.contains("The replay matches the recording."),
+        "{}",
+        stdout
+    );
+    assert!(replayed.status.success());
+
+    // A log whose recorded turns no longer follow from its input fails.
+    let tampered = std::fs::read_to_string(&log).unwrap().replacen(
+        "\"input\":\"5\\n\"",
+        "\"input\":\"6\\n\"",
+        1,
+    );
+    std::fs::write(&log, tampered).unwrap();
+    let replayed = run(&["replay", "--speed=0", log_arg], "");
+    assert_eq!(replayed.status.code(), Some(1));
+    assert!(
+        String::from_utf8(replayed.stderr)
+            .unwrap()
+            .contains("no longer matches")
+    );
+}
+
+#[test]
+fn recordings_keep_the_name_prompt_and_save_lines() {
+    let data = data_dir("replay-save");
+    std::fs::create_dir_all(&data).unwrap();
+    let log = data.join("session.jsonl");
+    let save = data.join("game.save");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_guessing-game"))
+        .args([
+            "-d",
+            "easy",
+            "--seed",
+            "3",
+            "--record",
+            log.to_str().unwrap(),
+        ])
+        .env("NO_COLOR", "1")
+        .env("LC_ALL", "C")
+        .env("XDG_DATA_HOME", &data)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let typed = format!("ana\n5\n:save {}\n", save.display());
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(typed.as_bytes())
+        .unwrap();
+    assert!(child.wait_with_output().unwrap().status.success());
+    assert!(save.exists());
+
+    let replayed = run(&["replay", "--speed", "0", log.to_str().unwrap()], "");
+    let stdout = String::from_utf8(replayed.stdout).unwrap();
+    assert!(stdout.contains("What's your name?\n> ana"), "{}", stdout);
+    assert!(stdout.contains("> :save"), "{}", stdout);
+    assert!(
+        stdout.contains("The recording ends where the player typed :save"),
+        "{}",
+        stdout
+    );
+    assert!(replayed.status.success());
+}
+
+#[test]
+fn hot_seat_players_take_turns() {
+    let data = data_dir("hotseat");
+    let output = run_in(
+        &data,
+        &[
+            "--players",
+            "2",
+            "-d",
+            "easy",
+            "--attempts",
+            "10",
+            "--seed",
+            "8",
+        ],
+        "ana\nbo\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n",
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("ana, your guess:"), "{}", stdout);
+    assert!(stdout.contains("bo, your guess:"), "{}", stdout);
+    assert!(stdout.contains("Winner:"), "{}", stdout);
+    // Odd guesses are ana's and even ones are bo's, so the winner follows
+    // from the secret's parity.
+    let secret: u32 = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Winner: "))
+        .and_then(|line| line.split(" found ").nth(1))
+        .and_then(|rest| rest.trim_end_matches('!').parse().ok())
+        .unwrap();
+    let winner = if secret % 2 == 1 { "ana" } else { "bo" };
+    assert!(
+        stdout.contains(&format!("Winner: {} found", winner)),
+        "{}",
+        stdout
+    );
+
+    let stats = String::from_utf8(run_in(&data, &["--stats"], "").stdout).unwrap();
+    assert!(
+        stats.lines().any(|line| line.starts_with("ana")),
+        "{}",
+        stats
+    );
+    assert!(
+        stats.lines().any(|line| line.starts_with("bo")),
+        "{}",
+        stats
+    );
+}
+
+#[test]
+fn hot_seat_players_need_different_names() {
+    let output = run(
+        &["--players", "2", "-d", "easy", "--seed", "8"],
+        "ana\nana\n\n1\n",
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("ana is already playing, please pick another name."),
+        "{}",
+        stdout
+    );
+    assert_eq!(stdout.matches("Player 2, what's your name?").count(), 2);
+    assert!(stdout.contains("ana, your guess:"), "{}", stdout);
+}
@@ -0,0 +1,92 @@
+use guessing_game::Game;
+use guessing_game::GuessOutcome;
+use guessing_game::net::{Client, ClientMessage, Server, ServerMessage, Winner};
+use std::thread;
+
+fn start(secret: u32) -> (std::net::SocketAddr, thread::JoinHandle<Winner>) {
+    let server = Server::bind("127.0.0.1:0", Game::new(secret, 1..=100)).unwrap();
+    let addr = server.local_addr().unwrap();
+    let handle = thread::spawn(move || server.run(|_| {}).unwrap());
+    (addr, handle)
+}
+
+fn expect(client: &mut Client, expected: ServerMessage) {
+    assert_eq!(client.receive().unwrap(), Some(expected));
+}
+
+#[test]
+fn players_see_each_others_guesses_and_the_winner() {
+    let (addr, server) = start(42);
+
+    let (mut ana, range) = Client::join(addr, "ana").unwrap();
+    assert_eq!(range, (1, 100));
+    let (mut bo, _) = Client::join(addr, "bo").unwrap();
+    expect(&mut ana, ServerMessage::Joined("bo".into()));
+    let (mut cy, _) = Client::join(addr, "cy").unwrap();
+    expect(&mut ana, ServerMessage::Joined("cy".into()));
+    expect(&mut bo, ServerMessage::Joined("cy".into()));
+
+    ana.send(&ClientMessage::Guess(50)).unwrap();
+    let too_big = ServerMessage::Result {
+        guess: 50,
+        outcome: GuessOutcome::TooBig,
+        name: "ana".into(),
+    };
+    for client in [&mut ana, &mut bo, &mut cy] {
+        expect(client, too_big.clone());
+    }
+
+    bo.send(&ClientMessage::Guess(10)).unwrap();
+    let too_small = ServerMessage::Result {
+        guess: 10,
+        outcome: GuessOutcome::TooSmall,
+        name: "bo".into(),
+    };
+    for client in [&mut ana, &mut bo, &mut cy] {
+        expect(client, too_small.clone());
+    }
+
+    cy.send(&ClientMessage::Guess(42)).unwrap();
+    let win = ServerMessage::Win {
+        guess: 42,
+        attempts: 1,
+        name: "cy".into(),
+    };
+    for client in [&mut ana, &mut bo, &mut cy] {
+        expect(client, win.clone());
+        assert_eq!(client.receive().unwrap(), None);
+    }
+
+    assert_eq!(
+        server.join().unwrap(),
+        Winner {
+            name: "cy".into(),
+            secret: 42,
+            attempts: 1,
+            others: vec!["ana".into(), "bo".into()],
+        }
+    );
+}
+
+#[test]
+fn invalid_messages_get_an_error_reply() {
+    let (addr, server) = start(7);
+    let (mut ana, _) = Client::join(addr, "ana").unwrap();
+
+    ana.send(&ClientMessage::Guess(101)).unwrap();
+    assert!(matches!(
+        ana.receive().unwrap(),
+        Some(ServerMessage::Error(_))
+    ));
+    ana.send(&ClientMessage::Join("again".into())).unwrap();
+    expect(&mut ana, ServerMessage::Error("already joined".into()));
+    let taken = Client::join(addr, "ana").err().unwrap();
+    assert_eq!(taken.to_string(), "the name ana is already taken");
+
+    ana.send(&ClientMessage::Guess(7)).unwrap();
+    assert!(matches!(
+        ana.receive().unwrap(),
+        Some(ServerMessage::Win { .. })
+    ));
+    assert_eq!(server.join().unwrap().attempts, 1);
+}